@@ -1,8 +1,151 @@
+use crate::lower_bound::gap_percent;
+use crate::termination::{StopReason, Termination};
+use crate::trace::{ConvergenceTrace, TraceRecorder};
 use crate::tsplib::{Solution, TsplibInstance};
 use indicatif::{ProgressBar, ProgressStyle};
+use std::fmt;
 use std::time::{Duration, Instant};
 
-pub type ProgressCallback<'a> = &'a mut dyn FnMut(String);
+/// Structured notification emitted by the solvers while they run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// A main-loop iteration is about to run.
+    IterationStarted { iteration: usize, best_cost: i32 },
+    /// A main-loop iteration completed; `current_cost` is the solution it produced.
+    IterationFinished {
+        iteration: usize,
+        current_cost: i32,
+        best_cost: i32,
+    },
+    /// The best known cost improved.
+    NewBest {
+        iteration: usize,
+        cost: i32,
+        elapsed: Duration,
+    },
+    /// An adaptive component picked an operator (neighbourhood, perturbation, ...).
+    OperatorChosen {
+        iteration: usize,
+        operator: String,
+        probability: f64,
+    },
+    /// The perturbation strength (number of perturbation steps) used in an iteration.
+    PerturbationStrength { iteration: usize, strength: usize },
+    /// A population member was replaced by a new solution.
+    PopulationReplaced {
+        iteration: usize,
+        index: usize,
+        cost: i32,
+    },
+    /// A local search could not improve its solution any further.
+    LocalOptimumReached { cost: i32, iterations: usize },
+    /// The search ended.
+    Finished {
+        iterations: usize,
+        best_cost: i32,
+        stop_reason: StopReason,
+    },
+    /// Event of a nested search (e.g. local search inside a metaheuristic).
+    Nested {
+        source: String,
+        event: Box<ProgressEvent>,
+    },
+    /// Free-form status text that has no structured counterpart.
+    Message(String),
+}
+
+impl ProgressEvent {
+    /// Wraps `self` as an event of the nested search `source`.
+    pub fn nested(self, source: impl Into<String>) -> Self {
+        ProgressEvent::Nested {
+            source: source.into(),
+            event: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for ProgressEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressEvent::IterationStarted {
+                iteration,
+                best_cost,
+            } => write!(f, "[Iter {}] Best cost: {}", iteration, best_cost),
+            ProgressEvent::IterationFinished {
+                iteration,
+                current_cost,
+                best_cost,
+            } => write!(
+                f,
+                "[Iter {}] Current cost: {}, best cost: {}",
+                iteration, current_cost, best_cost
+            ),
+            ProgressEvent::NewBest {
+                iteration,
+                cost,
+                elapsed,
+            } => write!(
+                f,
+                "[Iter {}] New global best: {} (Elapsed: {:?})",
+                iteration, cost, elapsed
+            ),
+            ProgressEvent::OperatorChosen {
+                iteration,
+                operator,
+                probability,
+            } => write!(
+                f,
+                "[Iter {}] Applying {} (p={:.3})",
+                iteration, operator, probability
+            ),
+            ProgressEvent::PerturbationStrength {
+                iteration,
+                strength,
+            } => write!(f, "[Iter {}] Perturbation strength: {}", iteration, strength),
+            ProgressEvent::PopulationReplaced {
+                iteration,
+                index,
+                cost,
+            } => write!(
+                f,
+                "[Iter {}] Replaced population member {} with cost {}",
+                iteration, index, cost
+            ),
+            ProgressEvent::LocalOptimumReached { cost, iterations } => write!(
+                f,
+                "Local optimum reached after {} iterations. Cost: {}",
+                iterations, cost
+            ),
+            ProgressEvent::Finished {
+                iterations,
+                best_cost,
+                stop_reason,
+            } => write!(
+                f,
+                "[Finished] {} after {} iterations. Best cost: {}",
+                stop_reason, iterations, best_cost
+            ),
+            ProgressEvent::Nested { source, event } => write!(f, "[{}] {}", source, event),
+            ProgressEvent::Message(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+pub type ProgressCallback<'a> = &'a mut dyn FnMut(ProgressEvent);
+
+/// Solvers whose iterations are single random moves report iteration events only this often;
+/// `NewBest` is still reported on every improvement.
+pub const MOVE_REPORT_INTERVAL: usize = 100;
+
+/// Whether a solver iterating over single moves reports the start/end of `iteration`.
+pub fn reports_move_iteration(iteration: usize) -> bool {
+    iteration == 1 || iteration.is_multiple_of(MOVE_REPORT_INTERVAL)
+}
+
+/// Adapts a string sink (e.g. an indicatif progress bar) to a progress callback.
+pub fn formatted_progress<F: FnMut(String)>(mut sink: F) -> impl FnMut(ProgressEvent) {
+    move |event| sink(event.to_string())
+}
 
 pub trait TspAlgorithm {
     fn name(&self) -> &str;
@@ -14,12 +157,33 @@ pub trait TspAlgorithm {
     ) -> Solution;
 }
 
+/// Result of a budgeted run: the best solution plus how and when the search ended.
+#[derive(Debug, Clone)]
+pub struct SolveOutcome {
+    pub solution: Solution,
+    pub iterations: usize,
+    pub stop_reason: StopReason,
+}
+
+/// Algorithms that run until an explicit `Termination` criterion is met.
+pub trait BudgetedAlgorithm: TspAlgorithm {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome;
+}
+
 #[derive(Debug, Clone)]
 pub struct RunResult {
     pub cost: i32,
     pub solution: Solution,
     pub time_ms: u128,
     pub iterations: Option<usize>,
+    pub stop_reason: Option<StopReason>,
+    /// Recorded only when the experiment was run with `record_trace`.
+    pub trace: Option<ConvergenceTrace>,
 }
 
 #[derive(Debug, Clone)]
@@ -33,34 +197,38 @@ pub struct ExperimentStats {
     pub avg_time_ms: f64,
     pub avg_iterations: Option<f64>,
     pub num_runs: usize,
+    pub runs: Vec<RunResult>,
 }
 
 pub fn run_experiment(
     algorithm: &(dyn TspAlgorithm + Send + Sync),
     instance: &TsplibInstance,
     num_runs: usize,
+    record_trace: bool,
 ) -> ExperimentStats {
-    run_experiment_base(algorithm.name(), instance, num_runs, |progress_callback| {
+    run_experiment_base(algorithm.name(), instance, num_runs, record_trace, |progress_callback| {
         (
             algorithm.solve_with_feedback(instance, progress_callback),
             None,
+            None,
         )
     })
 }
 
-pub type TimedSolveFn<'a, T> =
-    Box<dyn Fn(&T, &TsplibInstance, ProgressCallback) -> (Solution, usize) + Send + Sync + 'a>;
-
-pub fn run_timed_experiment<T: Send + Sync + ?Sized>(
-    algorithm: &T,
-    timed_solve_fn: TimedSolveFn<T>,
+pub fn run_budgeted_experiment(
+    algorithm: &(dyn BudgetedAlgorithm + Send + Sync),
     instance: &TsplibInstance,
+    termination: &Termination,
     num_runs: usize,
-    algorithm_name: &str,
+    record_trace: bool,
 ) -> ExperimentStats {
-    run_experiment_base(algorithm_name, instance, num_runs, |progress_callback| {
-        let (solution, iterations) = timed_solve_fn(algorithm, instance, progress_callback);
-        (solution, Some(iterations))
+    run_experiment_base(algorithm.name(), instance, num_runs, record_trace, |progress_callback| {
+        let outcome = algorithm.solve_budgeted(instance, termination, progress_callback);
+        (
+            outcome.solution,
+            Some(outcome.iterations),
+            Some(outcome.stop_reason),
+        )
     })
 }
 
@@ -68,10 +236,11 @@ fn run_experiment_base<F>(
     algorithm_name: &str,
     instance: &TsplibInstance,
     num_runs: usize,
+    record_trace: bool,
     solve_fn: F,
 ) -> ExperimentStats
 where
-    F: Fn(ProgressCallback) -> (Solution, Option<usize>),
+    F: Fn(ProgressCallback) -> (Solution, Option<usize>, Option<StopReason>),
 {
     if num_runs == 0 {
         return ExperimentStats {
@@ -84,6 +253,7 @@ where
             avg_time_ms: 0.0,
             avg_iterations: None,
             num_runs: 0,
+            runs: Vec::new(),
         };
     }
 
@@ -104,11 +274,22 @@ where
     for run_index in 0..num_runs {
         let start = Instant::now();
 
-        let mut callback = |status: String| {
-            pb.set_message(format!("[Run {}/{}] {}", run_index + 1, num_runs, status));
-        };
+        // Formatting every event is wasted work when the bar is not drawn (e.g. no terminal)
+        let mut callback = (!pb.is_hidden()).then(|| {
+            formatted_progress(|status| {
+                pb.set_message(format!("[Run {}/{}] {}", run_index + 1, num_runs, status));
+            })
+        });
 
-        let (solution, iterations_opt) = solve_fn(&mut callback);
+        let mut recorder = record_trace.then(TraceRecorder::new);
+        let (solution, iterations_opt, stop_reason) = solve_fn(&mut |event| {
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(&event);
+            }
+            if let Some(callback) = callback.as_mut() {
+                callback(event);
+            }
+        });
         let elapsed = start.elapsed();
 
         assert!(
@@ -122,12 +303,28 @@ where
             solution,
             time_ms: elapsed.as_millis(),
             iterations: iterations_opt,
+            stop_reason,
+            trace: recorder.map(TraceRecorder::finish),
         };
         results.push(result);
         pb.inc(1);
         pb.set_message("Done run.");
+
+        // The cancelled run still counts; the remaining ones are skipped.
+        if stop_reason == Some(StopReason::Cancelled) {
+            break;
+        }
     }
-    pb.finish_with_message("Finished all runs.");
+    if results.len() < num_runs {
+        pb.abandon_with_message(format!(
+            "Cancelled after {} of {} runs.",
+            results.len(),
+            num_runs
+        ));
+    } else {
+        pb.finish_with_message("Finished all runs.");
+    }
+    let num_runs = results.len();
 
     let mut min_cost = i32::MAX;
     let mut max_cost = i32::MIN;
@@ -168,13 +365,16 @@ where
         avg_time_ms: sum_time as f64 / num_runs as f64,
         avg_iterations,
         num_runs,
+        runs: results,
     }
 }
 
-pub fn format_stats_row(stats: &ExperimentStats) -> String {
+/// Formats one report row. With a `lower_bound` (see `lower_bound::best_lower_bound`), the
+/// last column shows the gap of the best and the average cost above it.
+pub fn format_stats_row(stats: &ExperimentStats, lower_bound: Option<i32>) -> String {
     if stats.num_runs == 0 {
         return format!(
-            "| {:<28} | No runs executed | N/A | N/A |",
+            "| {:<28} | No runs executed | N/A | N/A | N/A |",
             stats.algorithm_name
         );
     }
@@ -182,14 +382,23 @@ pub fn format_stats_row(stats: &ExperimentStats) -> String {
         Some(avg_iters) => format!("{:.1}", avg_iters),
         None => "N/A".to_string(),
     };
+    let gap_str = match lower_bound {
+        Some(bound) => format!(
+            "{:.2}% ({:.2}%)",
+            gap_percent(stats.min_cost as f64, bound),
+            gap_percent(stats.avg_cost, bound)
+        ),
+        None => "N/A".to_string(),
+    };
     let algo_name_padded = format!("{:<28}", stats.algorithm_name);
     format!(
-        "| {} | {} ({:.2} - {}) | {:>14.2} | {:>16} |",
+        "| {} | {} ({:.2} - {}) | {:>14.2} | {:>16} | {:>17} |",
         algo_name_padded,
         stats.min_cost,
         stats.avg_cost,
         stats.max_cost,
         stats.avg_time_ms,
-        iter_str
+        iter_str,
+        gap_str
     )
 }
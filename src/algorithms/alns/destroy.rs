@@ -0,0 +1,266 @@
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, RngCore};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Removes nodes from a solution; the removed nodes are reinserted by a `RepairOperator`.
+pub trait DestroyOperator: Send + Sync {
+    fn name(&self) -> String;
+    /// Removes `count` nodes from `solution` and returns them.
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize>;
+}
+
+/// The destroy operators ALNS uses unless configured otherwise.
+pub fn default_destroy_operators() -> Vec<Box<dyn DestroyOperator>> {
+    vec![
+        Box::new(RandomRemoval),
+        Box::new(WorstCostRemoval::new(3.0)),
+        Box::new(ShawRemoval::new(6.0)),
+        Box::new(SpatialClusterRemoval),
+        Box::new(RandomSegmentRemoval),
+    ]
+}
+
+fn remove_nodes(solution: &mut Solution, removed: &[usize]) {
+    let removed: HashSet<usize> = removed.iter().copied().collect();
+    solution.cycle1.retain(|v| !removed.contains(v));
+    solution.cycle2.retain(|v| !removed.contains(v));
+}
+
+/// Index into a list sorted by preference: `randomization = 1` picks uniformly, larger
+/// values concentrate the choice on the front of the list.
+fn biased_index(len: usize, randomization: f64, rng: &mut dyn RngCore) -> usize {
+    let y: f64 = rng.random();
+    ((y.powf(randomization) * len as f64) as usize).min(len - 1)
+}
+
+/// Cost saved by removing the node at `pos` from `cycle`.
+fn removal_gain(cycle: &[usize], pos: usize, instance: &TsplibInstance) -> i32 {
+    let n = cycle.len();
+    let v = cycle[pos];
+    match n {
+        1 => 0,
+        2 => 2 * instance.distance(cycle[1 - pos], v),
+        _ => {
+            let prev = cycle[(pos + n - 1) % n];
+            let next = cycle[(pos + 1) % n];
+            instance.distance(prev, v) + instance.distance(v, next) - instance.distance(prev, next)
+        }
+    }
+}
+
+/// Removes uniformly random nodes.
+#[derive(Debug, Clone)]
+pub struct RandomRemoval;
+
+impl DestroyOperator for RandomRemoval {
+    fn name(&self) -> String {
+        "RandomRemoval".to_string()
+    }
+
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        _instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize> {
+        let mut nodes: Vec<usize> = solution
+            .cycle1
+            .iter()
+            .chain(solution.cycle2.iter())
+            .copied()
+            .collect();
+        nodes.shuffle(rng);
+        nodes.truncate(count);
+        remove_nodes(solution, &nodes);
+        nodes
+    }
+}
+
+/// Repeatedly removes a node whose removal saves the most, with randomized rank selection.
+#[derive(Debug, Clone)]
+pub struct WorstCostRemoval {
+    randomization: f64,
+}
+
+impl WorstCostRemoval {
+    pub fn new(randomization: f64) -> Self {
+        Self { randomization }
+    }
+}
+
+impl DestroyOperator for WorstCostRemoval {
+    fn name(&self) -> String {
+        format!("WorstCostRemoval(p={})", self.randomization)
+    }
+
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize> {
+        let mut removed = Vec::with_capacity(count);
+        while removed.len() < count {
+            let mut gains: Vec<(i32, usize)> = [&solution.cycle1, &solution.cycle2]
+                .into_iter()
+                .flat_map(|cycle| {
+                    (0..cycle.len())
+                        .map(move |pos| (removal_gain(cycle, pos, instance), cycle[pos]))
+                })
+                .collect();
+            if gains.is_empty() {
+                break;
+            }
+            gains.sort_unstable_by_key(|&(gain, _)| Reverse(gain));
+            let node = gains[biased_index(gains.len(), self.randomization, rng)].1;
+            remove_nodes(solution, &[node]);
+            removed.push(node);
+        }
+        removed
+    }
+}
+
+/// Shaw removal: starts from a random node and keeps removing nodes related to the ones
+/// already removed. Relatedness combines distance with membership in the same cycle.
+#[derive(Debug, Clone)]
+pub struct ShawRemoval {
+    randomization: f64,
+}
+
+/// Relatedness penalty (as a fraction of the largest distance) for nodes in different cycles.
+const SHAW_CYCLE_WEIGHT: f64 = 0.5;
+
+impl ShawRemoval {
+    pub fn new(randomization: f64) -> Self {
+        Self { randomization }
+    }
+}
+
+impl DestroyOperator for ShawRemoval {
+    fn name(&self) -> String {
+        format!("ShawRemoval(p={})", self.randomization)
+    }
+
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize> {
+        let mut in_cycle1 = vec![false; instance.dimension];
+        for &v in &solution.cycle1 {
+            in_cycle1[v] = true;
+        }
+        let mut remaining: Vec<usize> = solution
+            .cycle1
+            .iter()
+            .chain(solution.cycle2.iter())
+            .copied()
+            .collect();
+        if remaining.is_empty() || count == 0 {
+            return Vec::new();
+        }
+        let max_distance = remaining
+            .iter()
+            .flat_map(|&u| remaining.iter().map(move |&v| instance.distance(u, v)))
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+        let relatedness = |u: usize, v: usize| {
+            let cycle_term = if in_cycle1[u] == in_cycle1[v] {
+                0.0
+            } else {
+                SHAW_CYCLE_WEIGHT
+            };
+            instance.distance(u, v) as f64 / max_distance + cycle_term
+        };
+
+        let seed = remaining.swap_remove(rng.random_range(0..remaining.len()));
+        let mut removed = vec![seed];
+        while removed.len() < count && !remaining.is_empty() {
+            let reference = *removed.choose(rng).unwrap();
+            remaining.sort_unstable_by(|&a, &b| {
+                relatedness(reference, a).total_cmp(&relatedness(reference, b))
+            });
+            let index = biased_index(remaining.len(), self.randomization, rng);
+            removed.push(remaining.remove(index));
+        }
+        remove_nodes(solution, &removed);
+        removed
+    }
+}
+
+/// Removes the nodes closest to a random centre, regardless of the cycle they belong to.
+#[derive(Debug, Clone)]
+pub struct SpatialClusterRemoval;
+
+impl DestroyOperator for SpatialClusterRemoval {
+    fn name(&self) -> String {
+        "SpatialClusterRemoval".to_string()
+    }
+
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize> {
+        let mut nodes: Vec<usize> = solution
+            .cycle1
+            .iter()
+            .chain(solution.cycle2.iter())
+            .copied()
+            .collect();
+        let Some(&centre) = nodes.choose(rng) else {
+            return Vec::new();
+        };
+        nodes.sort_unstable_by_key(|&v| instance.distance(centre, v));
+        nodes.truncate(count);
+        remove_nodes(solution, &nodes);
+        nodes
+    }
+}
+
+/// Removes one contiguous random segment from each cycle, splitting `count` between them.
+#[derive(Debug, Clone)]
+pub struct RandomSegmentRemoval;
+
+impl DestroyOperator for RandomSegmentRemoval {
+    fn name(&self) -> String {
+        "RandomSegmentRemoval".to_string()
+    }
+
+    fn destroy(
+        &self,
+        solution: &mut Solution,
+        _instance: &TsplibInstance,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<usize> {
+        let mut removed = Vec::with_capacity(count);
+        let counts = [count.div_ceil(2), count / 2];
+        for (cycle, segment_length) in [&solution.cycle1, &solution.cycle2].into_iter().zip(counts)
+        {
+            let n = cycle.len();
+            if n == 0 {
+                continue;
+            }
+            let start = rng.random_range(0..n);
+            removed.extend((0..segment_length.min(n)).map(|i| cycle[(start + i) % n]));
+        }
+        remove_nodes(solution, &removed);
+        removed
+    }
+}
@@ -0,0 +1,297 @@
+//! Adaptive large neighbourhood search.
+//!
+//! Every iteration destroys part of the current solution with one operator and repairs it
+//! with another, both picked by roulette wheel. Operators earn scores for new best, improving
+//! and accepted solutions; at the end of each segment the weights move towards the average
+//! score per use. Candidates are accepted with a simulated annealing criterion.
+
+pub mod destroy;
+pub mod repair;
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::LocalSearch;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use crate::utils::generate_random_solution;
+use destroy::DestroyOperator;
+use rand::{Rng, RngCore, rng};
+use repair::RepairOperator;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Iterations between weight updates.
+const SEGMENT_LENGTH: usize = 100;
+/// Share of the new segment score in the updated weight.
+const REACTION_FACTOR: f64 = 0.1;
+/// Scores for a new global best, an improvement of the current solution, and an accepted
+/// worse solution.
+const SCORE_NEW_BEST: f64 = 33.0;
+const SCORE_IMPROVED: f64 = 9.0;
+const SCORE_ACCEPTED: f64 = 13.0;
+/// The initial temperature accepts a solution this much worse than the first one with
+/// probability 1/2.
+const START_TEMPERATURE_WORSENING: f64 = 0.05;
+/// Weights never drop below this, so every operator keeps a chance to be picked.
+const MIN_WEIGHT: f64 = 0.1;
+
+/// Roulette-wheel weights and scores of the current segment.
+struct OperatorStats {
+    weights: Vec<f64>,
+    scores: Vec<f64>,
+    uses: Vec<usize>,
+}
+
+impl OperatorStats {
+    fn new(count: usize) -> Self {
+        Self {
+            weights: vec![1.0; count],
+            scores: vec![0.0; count],
+            uses: vec![0; count],
+        }
+    }
+
+    /// Picks an operator; returns its index and selection probability.
+    fn select(&mut self, rng: &mut dyn RngCore) -> (usize, f64) {
+        let total: f64 = self.weights.iter().sum();
+        let mut threshold = rng.random_range(0.0..total);
+        let mut chosen = self.weights.len() - 1;
+        for (index, &weight) in self.weights.iter().enumerate() {
+            if threshold < weight {
+                chosen = index;
+                break;
+            }
+            threshold -= weight;
+        }
+        self.uses[chosen] += 1;
+        (chosen, self.weights[chosen] / total)
+    }
+
+    fn reward(&mut self, index: usize, score: f64) {
+        self.scores[index] += score;
+    }
+
+    fn end_segment(&mut self) {
+        for ((weight, score), uses) in self
+            .weights
+            .iter_mut()
+            .zip(&mut self.scores)
+            .zip(&mut self.uses)
+        {
+            if *uses > 0 {
+                *weight = ((1.0 - REACTION_FACTOR) * *weight
+                    + REACTION_FACTOR * *score / *uses as f64)
+                    .max(MIN_WEIGHT);
+            }
+            *score = 0.0;
+            *uses = 0;
+        }
+    }
+}
+
+pub struct Alns {
+    destroy_operators: Vec<Box<dyn DestroyOperator>>,
+    repair_operators: Vec<Box<dyn RepairOperator>>,
+    /// Fraction of the nodes removed per iteration, drawn uniformly from this range.
+    destroy_fraction: RangeInclusive<f64>,
+    /// Temperature multiplier applied after every iteration.
+    cooling_rate: f64,
+    /// Improve every repaired solution with local search before acceptance.
+    local_search: Option<LocalSearch>,
+    name_str: String,
+}
+
+impl Alns {
+    pub fn new(
+        destroy_operators: Vec<Box<dyn DestroyOperator>>,
+        repair_operators: Vec<Box<dyn RepairOperator>>,
+        destroy_fraction: RangeInclusive<f64>,
+        cooling_rate: f64,
+        local_search: Option<LocalSearch>,
+    ) -> Self {
+        assert!(
+            !destroy_operators.is_empty() && !repair_operators.is_empty(),
+            "ALNS needs at least one destroy and one repair operator"
+        );
+        assert!(
+            *destroy_fraction.start() > 0.0
+                && destroy_fraction.start() <= destroy_fraction.end()
+                && *destroy_fraction.end() < 1.0,
+            "Destroy fraction must be a non-empty range within (0, 1)"
+        );
+        assert!(
+            cooling_rate > 0.0 && cooling_rate <= 1.0,
+            "Cooling rate must be within (0, 1]"
+        );
+        let destroy_names: Vec<String> = destroy_operators.iter().map(|op| op.name()).collect();
+        let repair_names: Vec<String> = repair_operators.iter().map(|op| op.name()).collect();
+        let ls_info = match &local_search {
+            Some(ls) => format!(", LS: {:?}/{:?}", ls.variant, ls.neighborhood),
+            None => String::new(),
+        };
+        let name_str = format!(
+            "ALNS (destroy: [{}], repair: [{}], fraction={:.2}-{:.2}, cooling={}{})",
+            destroy_names.join(", "),
+            repair_names.join(", "),
+            destroy_fraction.start(),
+            destroy_fraction.end(),
+            cooling_rate,
+            ls_info
+        );
+        Self {
+            destroy_operators,
+            repair_operators,
+            destroy_fraction,
+            cooling_rate,
+            local_search,
+            name_str,
+        }
+    }
+}
+
+impl BudgetedAlgorithm for Alns {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+
+        let mut current_solution = generate_random_solution(instance);
+        if let Some(ls) = &self.local_search {
+            current_solution = ls
+                .solve_from_solution_until(
+                    instance,
+                    current_solution,
+                    &ls_termination,
+                    &mut |event| progress_callback(event.nested("Initial LS")),
+                )
+                .solution;
+        }
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress.record_evaluation(current_cost);
+
+        let mut temperature = -START_TEMPERATURE_WORSENING * current_cost as f64 / 0.5f64.ln();
+        let mut destroy_stats = OperatorStats::new(self.destroy_operators.len());
+        let mut repair_stats = OperatorStats::new(self.repair_operators.len());
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let (destroy_index, destroy_probability) = destroy_stats.select(&mut rng);
+            let (repair_index, repair_probability) = repair_stats.select(&mut rng);
+            let destroy_op = &self.destroy_operators[destroy_index];
+            let repair_op = &self.repair_operators[repair_index];
+            progress_callback(ProgressEvent::OperatorChosen {
+                iteration: iterations,
+                operator: destroy_op.name(),
+                probability: destroy_probability,
+            });
+            progress_callback(ProgressEvent::OperatorChosen {
+                iteration: iterations,
+                operator: repair_op.name(),
+                probability: repair_probability,
+            });
+
+            let fraction = rng.random_range(self.destroy_fraction.clone());
+            let count = ((instance.size() as f64 * fraction).round() as usize).max(1);
+            let mut candidate = current_solution.clone();
+            let removed = destroy_op.destroy(&mut candidate, instance, count, &mut rng);
+            repair_op.repair(&mut candidate, instance, removed, &mut rng);
+            if let Some(ls) = &self.local_search {
+                candidate = ls
+                    .solve_from_solution_until(instance, candidate, &ls_termination, &mut |event| {
+                        progress_callback(
+                            event.nested(format!("Iter {} LS on repaired", iterations)),
+                        )
+                    })
+                    .solution;
+            }
+            let candidate_cost = candidate.calculate_cost(instance);
+            progress.record_evaluation(candidate_cost);
+
+            let score = if candidate_cost < best_cost {
+                Some(SCORE_NEW_BEST)
+            } else if candidate_cost < current_cost {
+                Some(SCORE_IMPROVED)
+            } else if candidate_cost == current_cost
+                || rng.random_bool((-(candidate_cost - current_cost) as f64 / temperature).exp())
+            {
+                Some(SCORE_ACCEPTED)
+            } else {
+                None
+            };
+            if let Some(score) = score {
+                destroy_stats.reward(destroy_index, score);
+                repair_stats.reward(repair_index, score);
+                current_solution = candidate;
+                current_cost = candidate_cost;
+                if current_cost < best_cost {
+                    best_cost = current_cost;
+                    best_solution = current_solution.clone();
+                    progress_callback(ProgressEvent::NewBest {
+                        iteration: iterations,
+                        cost: best_cost,
+                        elapsed: progress.elapsed(),
+                    });
+                }
+            }
+            temperature *= self.cooling_rate;
+            if iterations.is_multiple_of(SEGMENT_LENGTH) {
+                destroy_stats.end_segment();
+                repair_stats.end_segment();
+            }
+
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for Alns {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -0,0 +1,166 @@
+use crate::moves::types::CycleId;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+
+/// Reinserts the nodes removed by a `DestroyOperator`, keeping the cycles balanced.
+pub trait RepairOperator: Send + Sync {
+    fn name(&self) -> String;
+    fn repair(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        removed: Vec<usize>,
+        rng: &mut dyn RngCore,
+    );
+}
+
+/// The repair operators ALNS uses unless configured otherwise.
+pub fn default_repair_operators() -> Vec<Box<dyn RepairOperator>> {
+    vec![
+        Box::new(GreedyRepair),
+        Box::new(RegretRepair::new(2, 0.0)),
+        Box::new(RegretRepair::new(3, 0.0)),
+        Box::new(RegretRepair::new(2, 0.025)),
+    ]
+}
+
+/// Cheapest insertion: repeatedly performs the cheapest insertion of any removed node.
+#[derive(Debug, Clone)]
+pub struct GreedyRepair;
+
+impl RepairOperator for GreedyRepair {
+    fn name(&self) -> String {
+        "GreedyRepair".to_string()
+    }
+
+    fn repair(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        removed: Vec<usize>,
+        rng: &mut dyn RngCore,
+    ) {
+        regret_insertion(solution, instance, removed, 1, 0.0, rng);
+    }
+}
+
+/// Regret-k insertion: inserts first the node that loses the most by not getting one of
+/// its `k` best positions. With `noise > 0`, every insertion cost is perturbed by up to
+/// `noise` times the largest distance, which diversifies the repaired solutions.
+#[derive(Debug, Clone)]
+pub struct RegretRepair {
+    k: usize,
+    noise: f64,
+}
+
+impl RegretRepair {
+    pub fn new(k: usize, noise: f64) -> Self {
+        assert!(
+            k >= 2,
+            "Regret repair needs k >= 2; use GreedyRepair for k = 1"
+        );
+        Self { k, noise }
+    }
+}
+
+impl RepairOperator for RegretRepair {
+    fn name(&self) -> String {
+        if self.noise > 0.0 {
+            format!("Regret{}Repair(noise={})", self.k, self.noise)
+        } else {
+            format!("Regret{}Repair", self.k)
+        }
+    }
+
+    fn repair(
+        &self,
+        solution: &mut Solution,
+        instance: &TsplibInstance,
+        removed: Vec<usize>,
+        rng: &mut dyn RngCore,
+    ) {
+        regret_insertion(solution, instance, removed, self.k, self.noise, rng);
+    }
+}
+
+/// Inserts all `remaining` nodes, choosing the node with the largest regret-k value
+/// (ties broken by the cheaper best insertion). `k = 1` is plain cheapest insertion.
+/// Cycles never grow beyond their balanced target sizes.
+fn regret_insertion(
+    solution: &mut Solution,
+    instance: &TsplibInstance,
+    mut remaining: Vec<usize>,
+    k: usize,
+    noise: f64,
+    rng: &mut dyn RngCore,
+) {
+    let target1 = instance.size().div_ceil(2);
+    let target2 = instance.size() - target1;
+    let noise_amplitude = if noise > 0.0 {
+        let max_distance = (0..instance.size())
+            .flat_map(|u| (0..instance.size()).map(move |v| (u, v)))
+            .map(|(u, v)| instance.distance(u, v))
+            .max()
+            .unwrap_or(0);
+        noise * max_distance as f64
+    } else {
+        0.0
+    };
+
+    while !remaining.is_empty() {
+        // (regret, best cost, index in remaining, cycle, insert position)
+        let mut best: Option<(f64, f64, usize, CycleId, usize)> = None;
+        for (index, &node) in remaining.iter().enumerate() {
+            let mut options: Vec<(f64, CycleId, usize)> = Vec::new();
+            for (cycle_id, target) in [(CycleId::Cycle1, target1), (CycleId::Cycle2, target2)] {
+                let cycle = solution.get_cycle(cycle_id);
+                let n = cycle.len();
+                if n >= target {
+                    continue;
+                }
+                if n == 0 {
+                    options.push((0.0, cycle_id, 0));
+                    continue;
+                }
+                for i in 0..n {
+                    let (prev, next) = (cycle[i], cycle[(i + 1) % n]);
+                    let mut cost = (instance.distance(prev, node) + instance.distance(node, next)
+                        - instance.distance(prev, next)) as f64;
+                    if noise_amplitude > 0.0 {
+                        cost += noise_amplitude * rng.random_range(-1.0..=1.0);
+                    }
+                    options.push((cost, cycle_id, i + 1));
+                }
+            }
+            if options.is_empty() {
+                continue;
+            }
+            let considered = k.min(options.len());
+            if considered < options.len() {
+                options.select_nth_unstable_by(considered - 1, |a, b| a.0.total_cmp(&b.0));
+            }
+            options[..considered].sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+            let (best_cost, cycle_id, position) = options[0];
+            let regret: f64 = options[1..considered]
+                .iter()
+                .map(|option| option.0 - best_cost)
+                .sum();
+            let better = best.is_none_or(|(best_regret, best_best_cost, ..)| {
+                regret > best_regret || (regret == best_regret && best_cost < best_best_cost)
+            });
+            if better {
+                best = Some((regret, best_cost, index, cycle_id, position));
+            }
+        }
+
+        let Some((_, _, index, cycle_id, position)) = best else {
+            eprintln!(
+                "[WARN] Regret repair could not place {} nodes: both cycles are full.",
+                remaining.len()
+            );
+            break;
+        };
+        let node = remaining.swap_remove(index);
+        solution.get_cycle_mut(cycle_id).insert(position, node);
+    }
+}
@@ -0,0 +1,357 @@
+//! MAX-MIN ant system for two cycles.
+//!
+//! Each ant grows both cycles at once from two distant start nodes, always extending the
+//! cycle that is further from its balanced target size. The next node is drawn among the
+//! unvisited `k` nearest neighbours of the cycle's last node with probability proportional
+//! to `tau^alpha * (1 / d)^beta`; only if all of them are taken does the ant fall back to
+//! the best unvisited node overall. The iteration-best ant is improved with local search
+//! and, alternately with the global best, deposits pheromone. Trails are kept within
+//! `[tau_min, tau_max]` and reset when the search stagnates.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::LocalSearch;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+use std::time::Duration;
+
+/// Probability that an ant at convergence builds the best solution; sets `tau_min`.
+const P_BEST: f64 = 0.05;
+/// Every this many iterations the global best instead of the iteration best deposits.
+const GLOBAL_BEST_PERIOD: usize = 5;
+/// Reset the trails to `tau_max` after this many iterations without a new best.
+const STAGNATION_RESET: usize = 100;
+
+pub struct AntColony {
+    local_search: LocalSearch,
+    num_ants: usize,
+    /// Weight of the pheromone in the transition rule.
+    alpha: f64,
+    /// Weight of the inverse distance in the transition rule.
+    beta: f64,
+    /// Fraction of the pheromone that evaporates every iteration.
+    evaporation: f64,
+    /// Size of the candidate lists. Requires `precompute_nearest_neighbors` on the instance.
+    candidate_neighbors: usize,
+    name_str: String,
+}
+
+impl AntColony {
+    pub fn new(
+        local_search: LocalSearch,
+        num_ants: usize,
+        alpha: f64,
+        beta: f64,
+        evaporation: f64,
+        candidate_neighbors: usize,
+    ) -> Self {
+        assert!(num_ants > 0, "ACO needs at least one ant");
+        assert!(
+            evaporation > 0.0 && evaporation < 1.0,
+            "Evaporation rate must be within (0, 1)"
+        );
+        let name_str = format!(
+            "MMAS (ants={}, alpha={}, beta={}, rho={}, k={}, ls_var={:?}, ls_neigh={:?})",
+            num_ants,
+            alpha,
+            beta,
+            evaporation,
+            candidate_neighbors,
+            local_search.variant,
+            local_search.neighborhood
+        );
+        Self {
+            local_search,
+            num_ants,
+            alpha,
+            beta,
+            evaporation,
+            candidate_neighbors,
+            name_str,
+        }
+    }
+
+    /// `tau_max` and `tau_min` for the given best cost.
+    fn trail_limits(&self, best_cost: i32, n: usize) -> (f64, f64) {
+        let tau_max = 1.0 / (self.evaporation * best_cost.max(1) as f64);
+        let p_root = P_BEST.powf(1.0 / n as f64);
+        let average_choices = (n as f64 / 2.0).max(2.0);
+        let tau_min = (tau_max * (1.0 - p_root) / ((average_choices - 1.0) * p_root)).min(tau_max);
+        (tau_max, tau_min)
+    }
+
+    /// Builds one solution with both cycles grown in lockstep.
+    fn construct<R: Rng + ?Sized>(
+        &self,
+        instance: &TsplibInstance,
+        weights: &Pheromones,
+        rng: &mut R,
+    ) -> Solution {
+        let n = instance.size();
+        let targets = [n.div_ceil(2), n / 2];
+        let mut visited = vec![false; n];
+        let mut cycles = targets.map(Vec::with_capacity);
+
+        let start1 = rng.random_range(0..n);
+        cycles[0].push(start1);
+        visited[start1] = true;
+        if targets[1] > 0 {
+            let start2 = (0..n)
+                .filter(|&v| v != start1)
+                .max_by_key(|&v| instance.distance(start1, v))
+                .expect("n >= 2 when the second cycle is non-empty");
+            cycles[1].push(start2);
+            visited[start2] = true;
+        }
+
+        let mut candidates: Vec<(usize, f64)> = Vec::with_capacity(self.candidate_neighbors);
+        while cycles[0].len() + cycles[1].len() < n {
+            // Extend the cycle that is relatively less complete
+            let c = if cycles[1].len() >= targets[1] {
+                0
+            } else if cycles[0].len() >= targets[0] {
+                1
+            } else if cycles[0].len() * targets[1] <= cycles[1].len() * targets[0] {
+                0
+            } else {
+                1
+            };
+            let last = *cycles[c].last().unwrap();
+
+            candidates.clear();
+            candidates.extend(
+                instance
+                    .get_nearest_neighbors(last)
+                    .iter()
+                    .take(self.candidate_neighbors)
+                    .filter(|&&v| !visited[v])
+                    .map(|&v| (v, weights.attractiveness(last, v))),
+            );
+            let next = if candidates.is_empty() {
+                (0..n)
+                    .filter(|&v| !visited[v])
+                    .max_by(|&a, &b| {
+                        weights
+                            .attractiveness(last, a)
+                            .total_cmp(&weights.attractiveness(last, b))
+                    })
+                    .expect("an unvisited node remains")
+            } else {
+                let total: f64 = candidates.iter().map(|&(_, w)| w).sum();
+                let mut threshold = rng.random_range(0.0..=total);
+                let mut chosen = candidates[candidates.len() - 1].0;
+                for &(v, w) in &candidates {
+                    if threshold <= w {
+                        chosen = v;
+                        break;
+                    }
+                    threshold -= w;
+                }
+                chosen
+            };
+            cycles[c].push(next);
+            visited[next] = true;
+        }
+
+        let [cycle1, cycle2] = cycles;
+        Solution::new(cycle1, cycle2)
+    }
+}
+
+/// Symmetric pheromone matrix together with the cached transition weights
+/// `tau^alpha * (1 / d)^beta`.
+struct Pheromones {
+    n: usize,
+    trails: Vec<f64>,
+    heuristic: Vec<f64>,
+    weights: Vec<f64>,
+    alpha: f64,
+}
+
+impl Pheromones {
+    fn new(instance: &TsplibInstance, alpha: f64, beta: f64, initial: f64) -> Self {
+        let n = instance.size();
+        let heuristic: Vec<f64> = (0..n * n)
+            .map(|i| (1.0 / instance.distance(i / n, i % n).max(1) as f64).powf(beta))
+            .collect();
+        let mut pheromones = Self {
+            n,
+            trails: vec![initial; n * n],
+            heuristic,
+            weights: vec![0.0; n * n],
+            alpha,
+        };
+        pheromones.refresh_weights();
+        pheromones
+    }
+
+    fn attractiveness(&self, from: usize, to: usize) -> f64 {
+        self.weights[from * self.n + to]
+    }
+
+    fn reset(&mut self, value: f64) {
+        self.trails.fill(value);
+        self.refresh_weights();
+    }
+
+    /// Evaporates all trails, reinforces the edges of `solution` and clamps the result.
+    fn update(&mut self, solution: &Solution, cost: i32, evaporation: f64, limits: (f64, f64)) {
+        let (tau_max, tau_min) = limits;
+        for trail in &mut self.trails {
+            *trail *= 1.0 - evaporation;
+        }
+        let deposit = 1.0 / cost.max(1) as f64;
+        for cycle in [&solution.cycle1, &solution.cycle2] {
+            let len = cycle.len();
+            if len < 2 {
+                continue;
+            }
+            for i in 0..len {
+                let (u, v) = (cycle[i], cycle[(i + 1) % len]);
+                self.trails[u * self.n + v] += deposit;
+                self.trails[v * self.n + u] += deposit;
+            }
+        }
+        for trail in &mut self.trails {
+            *trail = trail.clamp(tau_min, tau_max);
+        }
+        self.refresh_weights();
+    }
+
+    fn refresh_weights(&mut self) {
+        for ((weight, &trail), &heuristic) in self
+            .weights
+            .iter_mut()
+            .zip(&self.trails)
+            .zip(&self.heuristic)
+        {
+            *weight = trail.powf(self.alpha) * heuristic;
+        }
+    }
+}
+
+impl BudgetedAlgorithm for AntColony {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let n = instance.size();
+
+        // Uniform trails for the first iteration; they are set to tau_max once a cost is known
+        let mut pheromones = Pheromones::new(instance, self.alpha, self.beta, 1.0);
+        let mut best_solution: Option<Solution> = None;
+        let mut best_cost = i32::MAX;
+        let mut limits = (1.0, 1.0);
+        let mut iterations_since_best = 0;
+
+        // At least one iteration is always run, so there is a solution to return
+        let stop_reason = loop {
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let mut iteration_best: Option<(Solution, i32)> = None;
+            for _ in 0..self.num_ants {
+                let solution = self.construct(instance, &pheromones, &mut rng);
+                let cost = solution.calculate_cost(instance);
+                progress.record_evaluation(cost);
+                if iteration_best.as_ref().is_none_or(|(_, c)| cost < *c) {
+                    iteration_best = Some((solution, cost));
+                }
+            }
+            let (constructed, _) = iteration_best.expect("at least one ant");
+            let iteration_solution = self
+                .local_search
+                .solve_from_solution_until(instance, constructed, &ls_termination, &mut |event| {
+                    progress_callback(
+                        event.nested(format!("Iter {} LS on iteration best", iterations)),
+                    )
+                })
+                .solution;
+            let current_cost = iteration_solution.calculate_cost(instance);
+            progress.record_evaluation(current_cost);
+
+            if current_cost < best_cost {
+                best_cost = current_cost;
+                best_solution = Some(iteration_solution.clone());
+                limits = self.trail_limits(best_cost, n);
+                iterations_since_best = 0;
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            } else {
+                iterations_since_best += 1;
+            }
+
+            if iterations == 1 {
+                pheromones.reset(limits.0);
+            }
+            if iterations_since_best >= STAGNATION_RESET {
+                progress_callback(ProgressEvent::Message(format!(
+                    "[Iter {}] No new best for {} iterations, resetting trails.",
+                    iterations, iterations_since_best
+                )));
+                pheromones.reset(limits.0);
+                iterations_since_best = 0;
+            } else if iterations.is_multiple_of(GLOBAL_BEST_PERIOD) {
+                let best = best_solution.as_ref().expect("set in the first iteration");
+                pheromones.update(best, best_cost, self.evaporation, limits);
+            } else {
+                pheromones.update(&iteration_solution, current_cost, self.evaporation, limits);
+            }
+
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution.expect("ACO runs at least one iteration"),
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for AntColony {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
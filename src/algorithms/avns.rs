@@ -1,12 +1,15 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
 use crate::algorithms::local_search::base::{
     HeuristicAlgorithm, InitialSolutionType, LocalSearch, NeighborhoodType, SearchVariant,
 };
 use crate::algorithms::perturbation::{Perturbation, SmallPerturbation};
+use crate::termination::{SearchProgress, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 use rand::{thread_rng, Rng};
 use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 /// Adaptive Variable Neighborhood Search with Learning
 pub struct Avns {
@@ -14,6 +17,8 @@ pub struct Avns {
     search_variant: SearchVariant,
     perturbation_strength: usize,
     learning_rate: f64,
+    /// Iterations without improvement before the current solution is shaken.
+    max_no_improvement: usize,
     name_str: String,
 }
 
@@ -23,40 +28,93 @@ impl Avns {
         search_variant: SearchVariant,
         perturbation_strength: usize,
         learning_rate: f64,
+        max_no_improvement: usize,
     ) -> Self {
         let name_str = format!(
-            "AVNS-L (neighborhoods: {:?}, variant: {:?}, perturb: {}, lr: {})",
-            neighborhoods, search_variant, perturbation_strength, learning_rate
+            "AVNS-L (neighborhoods: {:?}, variant: {:?}, perturb: {}, lr: {}, shake after: {})",
+            neighborhoods, search_variant, perturbation_strength, learning_rate, max_no_improvement
         );
         Self {
             neighborhoods,
             search_variant,
             perturbation_strength,
             learning_rate,
+            max_no_improvement,
             name_str,
         }
     }
 
-    pub fn solve_timed(
+    /// Roulette-wheel choice of a neighborhood; returns its index and selection probability.
+    fn select_neighborhood_adaptive<R: Rng>(
+        &self,
+        stats: &HashMap<usize, (usize, i32)>,
+        rng: &mut R,
+    ) -> (usize, f64) {
+        // Calculate scores for each neighborhood
+        let mut scores: Vec<(usize, f64)> = Vec::new();
+        
+        for idx in 0..self.neighborhoods.len() {
+            let (uses, improvement) = stats.get(&idx).unwrap();
+            let score = if *uses == 0 {
+                1.0 // Give unexplored neighborhoods a chance
+            } else {
+                // Score based on average improvement and exploration bonus
+                let avg_improvement = (*improvement as f64) / (*uses as f64);
+                let exploration_bonus = 1.0 / ((*uses as f64).sqrt() + 1.0);
+                avg_improvement.max(0.0) + exploration_bonus * self.learning_rate
+            };
+            scores.push((idx, score));
+        }
+        
+        // Use roulette wheel selection
+        let total_score: f64 = scores.iter().map(|(_, s)| s).sum();
+        if total_score <= 0.0 {
+            // If all scores are non-positive, select randomly
+            return (
+                rng.gen_range(0..self.neighborhoods.len()),
+                1.0 / self.neighborhoods.len() as f64,
+            );
+        }
+        
+        let mut cumulative = 0.0;
+        let random_value = rng.gen_range(0.0..1.0) * total_score;
+        
+        for &(idx, score) in &scores {
+            cumulative += score;
+            if cumulative >= random_value {
+                return (idx, score / total_score);
+            }
+        }
+        
+        // Fallback (should not reach here)
+        let last = self.neighborhoods.len() - 1;
+        (last, scores[last].1 / total_score)
+    }
+
+}
+
+impl BudgetedAlgorithm for Avns {
+    fn solve_budgeted(
         &self,
         instance: &TsplibInstance,
-        time_limit: Duration,
+        termination: &Termination,
         mut progress_callback: ProgressCallback,
-    ) -> (Solution, usize) {
-        let start_time = Instant::now();
+    ) -> SolveOutcome {
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
         let mut rng = thread_rng();
         
         // Initialize with heuristic solution
-        progress_callback("[AVNS-L] Generating initial solution with Weighted Regret...".to_string());
+        progress_callback(ProgressEvent::Message("[AVNS-L] Generating initial solution with Weighted Regret...".to_string()));
         let initial_ls = LocalSearch::new(
             self.search_variant,
             self.neighborhoods[0],
             InitialSolutionType::Heuristic(HeuristicAlgorithm::WeightedRegret),
             false,
         );
-        let mut current_solution = initial_ls.solve_with_feedback(instance, &mut |s| {
-            progress_callback(format!("[AVNS-L Init] {}", s))
-        });
+        let mut current_solution = initial_ls.solve_budgeted(instance, &ls_termination, &mut |event| {
+            progress_callback(event.nested("AVNS-L Init"))
+        }).solution;
         let mut current_cost = current_solution.calculate_cost(instance);
         
         let mut best_solution = current_solution.clone();
@@ -67,29 +125,36 @@ impl Avns {
         for i in 0..self.neighborhoods.len() {
             neighborhood_stats.insert(i, (0, 0));
         }
-        
-        let mut iterations = 0;
+        progress.record_evaluation(best_cost);
         let mut no_improvement_count = 0;
-        let max_no_improvement = 10;
         
-        while start_time.elapsed() < time_limit {
-            iterations += 1;
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
             let mut improved = false;
             
             // Select neighborhood based on performance (adaptive selection)
-            let neighborhood_idx = if iterations < self.neighborhoods.len() * 2 {
+            let (neighborhood_idx, choice_probability) = if iterations < self.neighborhoods.len() * 2 {
                 // Exploration phase: try all neighborhoods equally
-                iterations % self.neighborhoods.len()
+                (iterations % self.neighborhoods.len(), 1.0)
             } else {
                 // Exploitation phase: select based on performance
                 self.select_neighborhood_adaptive(&neighborhood_stats, &mut rng)
             };
             
             let neighborhood = self.neighborhoods[neighborhood_idx];
-            progress_callback(format!(
-                "[AVNS-L Iter {}] Trying neighborhood {:?}, current cost: {}",
-                iterations, neighborhood, current_cost
-            ));
+            progress_callback(ProgressEvent::OperatorChosen {
+                iteration: iterations,
+                operator: format!("{:?}", neighborhood),
+                probability: choice_probability,
+            });
             
             // Apply local search with selected neighborhood
             let ls = LocalSearch::new(
@@ -100,12 +165,14 @@ impl Avns {
             );
             
             // Apply local search starting from current solution
-            let ls_result = ls.solve_from_solution(
+            let ls_result = ls.solve_from_solution_until(
                 instance,
                 current_solution.clone(),
-                &mut |s| progress_callback(format!("[AVNS-L LS] {}", s)),
-            );
+                &ls_termination,
+                &mut |event| progress_callback(event.nested("AVNS-L LS")),
+            ).solution;
             let new_cost = ls_result.calculate_cost(instance);
+            progress.record_evaluation(new_cost);
             
             // Update statistics
             let improvement = current_cost - new_cost;
@@ -119,29 +186,30 @@ impl Avns {
                 improved = true;
                 no_improvement_count = 0;
                 
-                progress_callback(format!(
+                progress_callback(ProgressEvent::Message(format!(
                     "[AVNS-L Iter {}] Improved! New cost: {} (improvement: {})",
                     iterations, new_cost, improvement
-                ));
+                )));
                 
                 if new_cost < best_cost {
                     best_solution = current_solution.clone();
                     best_cost = new_cost;
-                    progress_callback(format!(
-                        "[AVNS-L Iter {}] New global best: {}",
-                        iterations, best_cost
-                    ));
+                    progress_callback(ProgressEvent::NewBest {
+                        iteration: iterations,
+                        cost: best_cost,
+                        elapsed: progress.elapsed(),
+                    });
                 }
             } else {
                 no_improvement_count += 1;
             }
             
             // Apply perturbation if stuck
-            if no_improvement_count >= max_no_improvement {
-                progress_callback(format!(
+            if no_improvement_count >= self.max_no_improvement {
+                progress_callback(ProgressEvent::Message(format!(
                     "[AVNS-L Iter {}] No improvement for {} iterations, applying perturbation...",
                     iterations, no_improvement_count
-                ));
+                )));
                 
                 // Apply smart perturbation
                 let perturbation_size = self.perturbation_strength + (no_improvement_count / 5);
@@ -150,22 +218,29 @@ impl Avns {
                 current_cost = current_solution.calculate_cost(instance);
                 
                 no_improvement_count = 0;
-                progress_callback(format!(
+                progress_callback(ProgressEvent::Message(format!(
                     "[AVNS-L Iter {}] After perturbation, cost: {}",
                     iterations, current_cost
-                ));
+                )));
             }
             
-            // Check time limit
-            if start_time.elapsed() >= time_limit {
-                break;
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+
+            // Check the termination criteria again
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
             }
-        }
+        };
         
-        progress_callback(format!(
-            "[AVNS-L] Finished. Iterations: {}, Best cost: {}",
-            iterations, best_cost
-        ));
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
         
         // Log neighborhood performance
         for (idx, (uses, improvement)) in &neighborhood_stats {
@@ -174,57 +249,18 @@ impl Avns {
             } else {
                 0.0
             };
-            progress_callback(format!(
+            progress_callback(ProgressEvent::Message(format!(
                 "[AVNS-L] Neighborhood {:?}: uses={}, avg_improvement={:.2}",
                 self.neighborhoods[*idx], uses, avg_improvement
-            ));
-        }
-        
-        (best_solution, iterations)
-    }
-    
-    fn select_neighborhood_adaptive<R: Rng>(
-        &self,
-        stats: &HashMap<usize, (usize, i32)>,
-        rng: &mut R,
-    ) -> usize {
-        // Calculate scores for each neighborhood
-        let mut scores: Vec<(usize, f64)> = Vec::new();
-        
-        for idx in 0..self.neighborhoods.len() {
-            let (uses, improvement) = stats.get(&idx).unwrap();
-            let score = if *uses == 0 {
-                1.0 // Give unexplored neighborhoods a chance
-            } else {
-                // Score based on average improvement and exploration bonus
-                let avg_improvement = (*improvement as f64) / (*uses as f64);
-                let exploration_bonus = 1.0 / ((*uses as f64).sqrt() + 1.0);
-                avg_improvement.max(0.0) + exploration_bonus * self.learning_rate
-            };
-            scores.push((idx, score));
+            )));
         }
         
-        // Use roulette wheel selection
-        let total_score: f64 = scores.iter().map(|(_, s)| s).sum();
-        if total_score <= 0.0 {
-            // If all scores are non-positive, select randomly
-            return rng.gen_range(0..self.neighborhoods.len());
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
         }
-        
-        let mut cumulative = 0.0;
-        let random_value = rng.gen_range(0.0..1.0) * total_score;
-        
-        for (idx, score) in scores {
-            cumulative += score;
-            if cumulative >= random_value {
-                return idx;
-            }
-        }
-        
-        // Fallback (should not reach here)
-        self.neighborhoods.len() - 1
     }
-
 }
 
 impl TspAlgorithm for Avns {
@@ -239,7 +275,7 @@ impl TspAlgorithm for Avns {
     ) -> Solution {
         // Default time limit based on instance size
         let time_limit = Duration::from_secs(60);
-        let (solution, _) = self.solve_timed(instance, time_limit, progress_callback);
-        solution
+        self.solve_budgeted(instance, &Termination::TimeLimit(time_limit), progress_callback)
+            .solution
     }
 } 
\ No newline at end of file
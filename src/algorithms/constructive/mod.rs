@@ -1 +1,2 @@
+pub mod route_first_cluster_second;
 pub mod weighted_regret_cycle;
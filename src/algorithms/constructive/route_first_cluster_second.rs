@@ -0,0 +1,246 @@
+//! Route-first cluster-second construction.
+//!
+//! A single tour through all nodes (the giant tour) is built first and then cut into two
+//! contiguous paths, each closed into a cycle. Cutting the giant tour removes two of its
+//! edges and adds the two closing edges, so every split is evaluated in `O(1)`. This is
+//! Bellman's split over the cut positions; because the balance constraint fixes both cycle
+//! sizes, the second cut follows from the first and the optimal split takes one `O(n)` pass.
+
+use crate::algorithm::{ProgressCallback, ProgressEvent, TspAlgorithm};
+use crate::termination::StopReason;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+
+/// Bits per axis of the grid the coordinates are mapped to for the Hilbert curve.
+const HILBERT_ORDER: u32 = 16;
+
+/// How the giant tour is built.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GiantTour {
+    /// Nearest neighbour tour from a random start node.
+    NearestNeighbor,
+    /// Nodes ordered along a Hilbert curve over the bounding box of the coordinates.
+    SpaceFillingCurve,
+}
+
+pub struct RouteFirstClusterSecond {
+    giant_tour: GiantTour,
+    name_str: String,
+}
+
+impl RouteFirstClusterSecond {
+    pub fn new(giant_tour: GiantTour) -> Self {
+        Self {
+            giant_tour,
+            name_str: format!("Route-First Cluster-Second ({:?})", giant_tour),
+        }
+    }
+
+    /// Builds the giant tour as a permutation of all nodes.
+    pub fn build_giant_tour<R: Rng + ?Sized>(
+        &self,
+        instance: &TsplibInstance,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        match self.giant_tour {
+            GiantTour::NearestNeighbor => nearest_neighbor_tour(instance, rng),
+            GiantTour::SpaceFillingCurve => hilbert_curve_tour(instance),
+        }
+    }
+}
+
+fn nearest_neighbor_tour<R: Rng + ?Sized>(instance: &TsplibInstance, rng: &mut R) -> Vec<usize> {
+    let n = instance.size();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut visited = vec![false; n];
+    let mut current = rng.random_range(0..n);
+    visited[current] = true;
+    let mut tour = Vec::with_capacity(n);
+    tour.push(current);
+    while tour.len() < n {
+        current = (0..n)
+            .filter(|&v| !visited[v])
+            .min_by_key(|&v| instance.distance(current, v))
+            .expect("an unvisited node remains");
+        visited[current] = true;
+        tour.push(current);
+    }
+    tour
+}
+
+fn hilbert_curve_tour(instance: &TsplibInstance) -> Vec<usize> {
+    let coordinates = &instance.coordinates[..instance.size()];
+    let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+    let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+    for &(x, y) in coordinates {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    // One scale for both axes keeps the curve's neighbourhoods square
+    let side = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+    let cells = ((1u64 << HILBERT_ORDER) - 1) as f64;
+    let mut keyed: Vec<(u64, usize)> = coordinates
+        .iter()
+        .enumerate()
+        .map(|(node, &(x, y))| {
+            let gx = ((x - min_x) / side * cells).round() as u64;
+            let gy = ((y - min_y) / side * cells).round() as u64;
+            (hilbert_index(gx, gy), node)
+        })
+        .collect();
+    keyed.sort_unstable();
+    keyed.into_iter().map(|(_, node)| node).collect()
+}
+
+/// Position of grid cell `(x, y)` along the Hilbert curve of order `HILBERT_ORDER`.
+fn hilbert_index(mut x: u64, mut y: u64) -> u64 {
+    let side = 1u64 << HILBERT_ORDER;
+    let mut index = 0;
+    let mut s = side / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+        // Rotate the quadrant so the sub-curve has the canonical orientation
+        if ry == 0 {
+            if rx == 1 {
+                x = side - 1 - x;
+                y = side - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    index
+}
+
+/// Optimally cuts `tour`, a permutation of all nodes, into two balanced cycles.
+///
+/// The first cycle is the contiguous stretch of `ceil(n / 2)` nodes (wrapping around the
+/// end of `tour`) whose removal and closing is cheapest; the second cycle is the rest.
+pub fn split_tour(instance: &TsplibInstance, tour: &[usize]) -> Solution {
+    let n = instance.size();
+    assert_eq!(tour.len(), n, "The giant tour must visit every node");
+    let mut seen = vec![false; n];
+    for &node in tour {
+        assert!(
+            node < n && !std::mem::replace(&mut seen[node], true),
+            "The giant tour must visit every node exactly once"
+        );
+    }
+    if n < 2 {
+        return Solution::new(tour.to_vec(), Vec::new());
+    }
+    let size1 = n.div_ceil(2);
+
+    let at = |position: usize| tour[position % n];
+    let (mut best_start, mut best_delta) = (0, i32::MAX);
+    for start in 0..n {
+        // First cycle: at(start) ..= at(start + size1 - 1); second cycle: the rest
+        let before = at(start + n - 1);
+        let first_end = at(start + size1 - 1);
+        let second_start = at(start + size1);
+        let delta = instance.distance(first_end, at(start))
+            + instance.distance(before, second_start)
+            - instance.distance(before, at(start))
+            - instance.distance(first_end, second_start);
+        if delta < best_delta {
+            best_delta = delta;
+            best_start = start;
+        }
+    }
+
+    let cycle1 = (best_start..best_start + size1).map(at).collect();
+    let cycle2 = (best_start + size1..best_start + n).map(at).collect();
+    Solution::new(cycle1, cycle2)
+}
+
+impl TspAlgorithm for RouteFirstClusterSecond {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let tour = self.build_giant_tour(instance, &mut rng());
+        let tour_cost = Solution::new(tour.clone(), Vec::new()).calculate_cost(instance);
+        progress_callback(ProgressEvent::Message(format!(
+            "[Route] Giant tour ({:?}) cost: {}",
+            self.giant_tour, tour_cost
+        )));
+
+        let solution = split_tour(instance, &tour);
+        let cost = solution.calculate_cost(instance);
+        progress_callback(ProgressEvent::Message(format!(
+            "[Cluster] Split into cycles of {} and {} nodes, cost: {}",
+            solution.cycle1.len(),
+            solution.cycle2.len(),
+            cost
+        )));
+        progress_callback(ProgressEvent::Finished {
+            iterations: 1,
+            best_cost: cost,
+            stop_reason: StopReason::Converged,
+        });
+        solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+
+    fn random_instance(n: usize, rng: &mut StdRng) -> TsplibInstance {
+        let coordinates = (0..n)
+            .map(|_| (rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect();
+        TsplibInstance::from_coordinates("random", coordinates)
+    }
+
+    /// Cost of the cheapest split, evaluating both cycles in full for every start position.
+    fn brute_force_split_cost(instance: &TsplibInstance, tour: &[usize]) -> i32 {
+        let n = tour.len();
+        let size1 = n.div_ceil(2);
+        (0..n)
+            .map(|start| {
+                let cycle1 = (start..start + size1).map(|i| tour[i % n]).collect();
+                let cycle2 = (start + size1..start + n).map(|i| tour[i % n]).collect();
+                Solution::new(cycle1, cycle2).calculate_cost(instance)
+            })
+            .min()
+            .expect("the tour is not empty")
+    }
+
+    #[test]
+    fn split_matches_brute_force_over_all_starts() {
+        let mut rng = StdRng::seed_from_u64(50);
+        for n in 4..=15 {
+            let instance = random_instance(n, &mut rng);
+            for _ in 0..10 {
+                let mut tour: Vec<usize> = (0..n).collect();
+                tour.shuffle(&mut rng);
+                let solution = split_tour(&instance, &tour);
+                assert!(solution.is_valid(&instance));
+                assert_eq!(solution.cycle1.len(), n.div_ceil(2));
+                assert_eq!(solution.cycle2.len(), n / 2);
+                assert_eq!(
+                    solution.calculate_cost(&instance),
+                    brute_force_split_cost(&instance, &tour),
+                    "n = {}, tour = {:?}",
+                    n,
+                    tour
+                );
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{ProgressCallback, ProgressEvent, TspAlgorithm};
+use crate::termination::StopReason;
 use crate::tsplib::{Solution, TsplibInstance};
-use rand::Rng;
-use rand::thread_rng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, rng};
 
 pub struct WeightedRegretCycle {
     pub k_regret: usize,
@@ -103,20 +104,66 @@ impl WeightedRegretCycle {
             .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
             .map(|(v, p, _)| (v, p))
     }
-}
 
-impl TspAlgorithm for WeightedRegretCycle {
-    fn name(&self) -> &str {
-        "Weighted 2-Regret Cycle"
+    /// Picks uniformly from the restricted candidate list: the vertices whose score is at
+    /// least `max - alpha * (max - min)`. `alpha = 0` is greedy, `alpha = 1` uniformly random.
+    fn select_rcl_vertex<R: Rng + ?Sized>(
+        &self,
+        cycle: &[usize],
+        available: &[usize],
+        instance: &TsplibInstance,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Option<(usize, usize)> {
+        let scored: Vec<(usize, usize, f64)> = available
+            .iter()
+            .map(|&vertex| {
+                let (score, pos) = self.calculate_weighted_score(vertex, cycle, instance);
+                (vertex, pos, score)
+            })
+            .collect();
+        let max = scored.iter().map(|c| c.2).fold(f64::MIN, f64::max);
+        let min = scored.iter().map(|c| c.2).fold(f64::MAX, f64::min);
+        let threshold = max - alpha * (max - min);
+        let rcl: Vec<&(usize, usize, f64)> = scored.iter().filter(|c| c.2 >= threshold).collect();
+        rcl.choose(rng).map(|&&(v, p, _)| (v, p))
     }
 
-    fn solve_with_feedback(
+    fn select_vertex<R: Rng + ?Sized>(
+        &self,
+        cycle: &[usize],
+        available: &[usize],
+        instance: &TsplibInstance,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Option<(usize, usize)> {
+        if alpha > 0.0 {
+            self.select_rcl_vertex(cycle, available, instance, alpha, rng)
+        } else {
+            self.select_best_vertex(cycle, available, instance)
+        }
+    }
+
+    /// Randomized construction for GRASP: every insertion is drawn from the restricted
+    /// candidate list of the weighted regret score (see `select_rcl_vertex`).
+    pub fn construct_randomized<R: Rng + ?Sized>(
         &self,
         instance: &TsplibInstance,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Solution {
+        self.construct(instance, alpha, rng, &mut |_| {})
+    }
+
+    fn construct<R: Rng + ?Sized>(
+        &self,
+        instance: &TsplibInstance,
+        alpha: f64,
+        rng: &mut R,
         progress_callback: ProgressCallback,
     ) -> Solution {
         let n = instance.size();
-        progress_callback(format!("[Init] Size: {}", n));
+        progress_callback(ProgressEvent::Message(format!("[Init] Size: {}", n)));
 
         if n == 0 {
             return Solution::new(vec![], vec![]);
@@ -125,8 +172,7 @@ impl TspAlgorithm for WeightedRegretCycle {
             return Solution::new(vec![0], vec![]);
         }
 
-        let mut rng = thread_rng();
-        let start1 = rng.gen_range(0..n);
+        let start1 = rng.random_range(0..n);
 
         let start2 = (0..n)
             .filter(|&j| j != start1)
@@ -138,19 +184,28 @@ impl TspAlgorithm for WeightedRegretCycle {
         let mut available: Vec<usize> = (0..n).filter(|&x| x != start1 && x != start2).collect();
         let initial_available_count = available.len();
 
-        progress_callback(format!("[Init] Start nodes: {}, {}", start1, start2));
+        progress_callback(ProgressEvent::Message(format!(
+            "[Init] Start nodes: {}, {}",
+            start1, start2
+        )));
 
         if !available.is_empty() {
             let nearest1 = self.find_nearest(start1, &available, instance);
             cycle1.push(nearest1);
             available.retain(|&x| x != nearest1);
-            progress_callback(format!("[Init Cycle 1] Added {}", nearest1));
+            progress_callback(ProgressEvent::Message(format!(
+                "[Init Cycle 1] Added {}",
+                nearest1
+            )));
 
             if !available.is_empty() {
                 let nearest2 = self.find_nearest(start2, &available, instance);
                 cycle2.push(nearest2);
                 available.retain(|&x| x != nearest2);
-                progress_callback(format!("[Init Cycle 2] Added {}", nearest2));
+                progress_callback(ProgressEvent::Message(format!(
+                    "[Init Cycle 2] Added {}",
+                    nearest2
+                )));
             }
         }
 
@@ -163,26 +218,26 @@ impl TspAlgorithm for WeightedRegretCycle {
             let progress_percent = (iterations_done * 100 / total_iterations.max(1));
 
             if current_cycle_id == 1 {
-                progress_callback(format!(
+                progress_callback(ProgressEvent::Message(format!(
                     "[{}% C1] Avail: {}",
                     progress_percent,
                     available.len()
-                ));
+                )));
                 if let Some((best_vertex, best_pos)) =
-                    self.select_best_vertex(&cycle1, &available, instance)
+                    self.select_vertex(&cycle1, &available, instance, alpha, rng)
                 {
                     cycle1.insert(best_pos, best_vertex);
                     available.retain(|&x| x != best_vertex);
                 }
                 current_cycle_id = 2;
             } else {
-                progress_callback(format!(
+                progress_callback(ProgressEvent::Message(format!(
                     "[{}% C2] Avail: {}",
                     progress_percent,
                     available.len()
-                ));
+                )));
                 if let Some((best_vertex, best_pos)) =
-                    self.select_best_vertex(&cycle2, &available, instance)
+                    self.select_vertex(&cycle2, &available, instance, alpha, rng)
                 {
                     cycle2.insert(best_pos, best_vertex);
                     available.retain(|&x| x != best_vertex);
@@ -190,7 +245,26 @@ impl TspAlgorithm for WeightedRegretCycle {
                 current_cycle_id = 1;
             }
         }
-        progress_callback("[Finished]".to_string());
-        Solution::new(cycle1, cycle2)
+        let solution = Solution::new(cycle1, cycle2);
+        progress_callback(ProgressEvent::Finished {
+            iterations: iterations_done,
+            best_cost: solution.calculate_cost(instance),
+            stop_reason: StopReason::Converged,
+        });
+        solution
+    }
+}
+
+impl TspAlgorithm for WeightedRegretCycle {
+    fn name(&self) -> &str {
+        "Weighted 2-Regret Cycle"
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        self.construct(instance, 0.0, &mut rng(), progress_callback)
     }
 }
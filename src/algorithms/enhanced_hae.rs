@@ -1,23 +1,22 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
-
-use crate::algorithms::local_search::base::{
-    HeuristicAlgorithm, InitialSolutionType, LocalSearch, NeighborhoodType, SearchVariant,
+use crate::algorithm::{BudgetedAlgorithm, ProgressCallback, SolveOutcome, TspAlgorithm};
+use crate::algorithms::evolutionary::improvement::{
+    AdaptiveLocalSearch, ChildImprovement, NeighborhoodChoice, PeriodicPerturbation,
 };
-use crate::algorithms::perturbation::{repair, Perturbation, SmallPerturbation};
-use crate::tsplib::{CycleId, Solution, TsplibInstance};
-use rand::{thread_rng, Rng};
-use std::collections::HashSet;
-use std::time::{Duration, Instant};
+use crate::algorithms::evolutionary::replacement::Replacement;
+use crate::algorithms::evolutionary::selection::ParentSelection;
+use crate::algorithms::evolutionary::{InitialPopulation, SteadyStateEvolution};
+use crate::algorithms::local_search::base::{LocalSearch, NeighborhoodType};
+use crate::algorithms::perturbation::SmallPerturbation;
+use crate::algorithms::recombination::Recombination;
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use std::time::Duration;
 
-/// Enhanced Hybrid Adaptive Evolution with advanced features
+/// Enhanced Hybrid Adaptive Evolution: children are improved with edge exchange or,
+/// if `adaptive_local_search`, with edge or vertex exchange chosen epsilon-greedily by
+/// their average improvement. The worst member is perturbed every 50 iterations.
 pub struct EnhancedHae {
-    base_local_search: LocalSearch,
-    pop_size: usize,
-    min_diff: i32,
-    edge_exchange_ls: LocalSearch,
-    vertex_exchange_ls: LocalSearch,
-    adaptive_local_search: bool,
-    elite_size: usize,
+    engine: SteadyStateEvolution,
     name_str: String,
 }
 
@@ -25,9 +24,10 @@ impl EnhancedHae {
     pub fn new(
         base_local_search: LocalSearch,
         pop_size: usize,
-        min_diff: i32,
         adaptive_local_search: bool,
-        elite_size: usize,
+        selection: Box<dyn ParentSelection>,
+        recombination: Box<dyn Recombination>,
+        replacement: Box<dyn Replacement>,
     ) -> Self {
         let edge_exchange_ls = LocalSearch::new(
             base_local_search.variant,
@@ -35,275 +35,56 @@ impl EnhancedHae {
             base_local_search.initial_solution_type,
             false,
         );
-        
         let vertex_exchange_ls = LocalSearch::new(
             base_local_search.variant,
             NeighborhoodType::VertexExchange,
             base_local_search.initial_solution_type,
             false,
         );
-        
+
         let name_str = format!(
-            "Enhanced-HAE (pop={}, min_diff={}, adaptive={}, elite={}, base_ls_neigh={:?})",
-            pop_size, min_diff, adaptive_local_search, elite_size, base_local_search.neighborhood
-        );
-        
-        Self {
-            base_local_search,
+            "Enhanced-HAE (pop={}, adaptive={}, select={}, recomb={}, replace={}, base_ls_neigh={:?})",
             pop_size,
-            min_diff,
-            edge_exchange_ls,
-            vertex_exchange_ls,
             adaptive_local_search,
-            elite_size,
-            name_str,
-        }
-    }
-    
-    pub fn solve_timed(
-        &self,
-        instance: &TsplibInstance,
-        time_limit: Duration,
-        mut progress_callback: ProgressCallback,
-    ) -> (Solution, usize) {
-        let mut rng = thread_rng();
-        let start_time = Instant::now();
-        
-        progress_callback("[Enhanced-HAE] Generating diverse initial population...".to_string());
-        let mut population: Vec<(Solution, i32)> = Vec::with_capacity(self.pop_size);
-        
-        let heuristic_count = self.pop_size / 4;
-        for i in 0..heuristic_count {
-            progress_callback(format!("[Init {}] Generating heuristic solution", i + 1));
-            let heuristic_ls = LocalSearch::new(
-                self.base_local_search.variant,
-                NeighborhoodType::EdgeExchange,
-                InitialSolutionType::Heuristic(HeuristicAlgorithm::WeightedRegret),
-                false,
-            );
-            let sol = heuristic_ls.solve_with_feedback(instance, &mut |s| {
-                progress_callback(format!("[Init Heuristic {}] {}", i + 1, s))
-            });
-            let cost = sol.calculate_cost(instance);
-            population.push((sol, cost));
-        }
-        
-        for i in heuristic_count..self.pop_size {
-            progress_callback(format!("[Init {}] Generating random LS solution", i + 1));
-            let sol = self.edge_exchange_ls.solve_with_feedback(instance, &mut |s| {
-                progress_callback(format!("[Init Random {}] {}", i + 1, s))
-            });
-            let cost = sol.calculate_cost(instance);
-            population.push((sol, cost));
-        }
-        
-        population.sort_by_key(|(_, cost)| *cost);
-        
-        let mut best_solution = population[0].0.clone();
-        let mut best_cost = population[0].1;
-        
-        let mut iterations = 0;
-        let mut neighborhood_performance = [0i32; 2];
-        let mut neighborhood_uses = [0usize; 2];
-        
-        while start_time.elapsed() < time_limit {
-            iterations += 1;
-            
-            let parent1_idx = self.tournament_selection(&population, 3, &mut rng);
-            let parent2_idx = self.tournament_selection(&population, 3, &mut rng);
-            
-            let parent1 = &population[parent1_idx].0;
-            let parent2 = &population[parent2_idx].0;
-            
-            progress_callback(format!(
-                "[Iter {}] Parents: idx {} (cost {}) and idx {} (cost {})",
-                iterations, parent1_idx, population[parent1_idx].1, 
-                parent2_idx, population[parent2_idx].1
-            ));
-            
-            let mut child = self.advanced_recombine(parent1, parent2, instance, &mut rng);
-            
-            if self.adaptive_local_search {
-                let neighborhood_choice = if neighborhood_uses[0] == 0 || neighborhood_uses[1] == 0 {
-                    if neighborhood_uses[0] == 0 { 0 } else { 1 }
-                } else {
-                    let avg_perf_0 = neighborhood_performance[0] as f64 / neighborhood_uses[0] as f64;
-                    let avg_perf_1 = neighborhood_performance[1] as f64 / neighborhood_uses[1] as f64;
-                    
-                    if rng.gen_bool(0.1) {
-                        rng.gen_range(0..2)
-                    } else {
-                        if avg_perf_0 > avg_perf_1 { 0 } else { 1 }
-                    }
-                };
-                
-                let cost_before_ls = child.calculate_cost(instance);
-                
-                child = if neighborhood_choice == 0 {
-                    progress_callback(format!("[Iter {}] Applying EdgeExchange LS", iterations));
-                    self.edge_exchange_ls.solve_from_solution(
-                        instance,
-                        child,
-                        &mut |s| progress_callback(format!("[Iter {} Edge-LS] {}", iterations, s)),
-                    )
-                } else {
-                    progress_callback(format!("[Iter {}] Applying VertexExchange LS", iterations));
-                    self.vertex_exchange_ls.solve_from_solution(
-                        instance,
-                        child,
-                        &mut |s| progress_callback(format!("[Iter {} Vertex-LS] {}", iterations, s)),
-                    )
-                };
-                
-                let cost_after_ls = child.calculate_cost(instance);
-                let improvement = cost_before_ls - cost_after_ls;
-                
-                neighborhood_performance[neighborhood_choice] += improvement;
-                neighborhood_uses[neighborhood_choice] += 1;
-                
-                progress_callback(format!(
-                    "[Iter {}] LS improvement: {} (neighborhood {})",
-                    iterations, improvement, neighborhood_choice
-                ));
-            } else {
-                child = self.edge_exchange_ls.solve_from_solution(
-                    instance,
-                    child,
-                    &mut |s| progress_callback(format!("[Iter {} LS] {}", iterations, s)),
-                );
-            }
-            
-            let child_cost = child.calculate_cost(instance);
-            
-            if child_cost < best_cost {
-                best_solution = child.clone();
-                best_cost = child_cost;
-                progress_callback(format!("[Iter {}] New global best: {}", iterations, best_cost));
-            }
-            
-            let too_similar = population.iter()
-                .take(self.elite_size)
-                .any(|(_, cost)| (child_cost - *cost).abs() < self.min_diff);
-            
-            if !too_similar && child_cost < population.last().unwrap().1 {
-                population.pop();
-                population.push((child, child_cost));
-                population.sort_by_key(|(_, cost)| *cost);
-                progress_callback(format!("[Iter {}] Child accepted with cost {}", iterations, child_cost));
-            } else if child_cost < population[population.len() / 2].1 {
-                let replace_idx = rng.gen_range(population.len() / 2..population.len());
-                population[replace_idx] = (child, child_cost);
-                population.sort_by_key(|(_, cost)| *cost);
-                progress_callback(format!("[Iter {}] Child replaced solution at idx {}", iterations, replace_idx));
-            }
-            
-            if iterations % 50 == 0 {
-                progress_callback(format!("[Iter {}] Injecting diversity...", iterations));
-                let worst_idx = population.len() - 1;
-                let perturbation = SmallPerturbation::new(10);
-                perturbation.perturb(&mut population[worst_idx].0, instance, &mut rng);
-                population[worst_idx].1 = population[worst_idx].0.calculate_cost(instance);
-            }
-        }
-        
-        progress_callback(format!(
-            "[Enhanced-HAE] Finished. Iterations: {}, Best cost: {}",
-            iterations, best_cost
-        ));
-        
-        if self.adaptive_local_search {
-            for i in 0..2 {
-                if neighborhood_uses[i] > 0 {
-                    let avg_perf = neighborhood_performance[i] as f64 / neighborhood_uses[i] as f64;
-                    progress_callback(format!(
-                        "[Enhanced-HAE] Neighborhood {}: uses={}, avg_improvement={:.2}",
-                        if i == 0 { "EdgeExchange" } else { "VertexExchange" },
-                        neighborhood_uses[i], avg_perf
-                    ));
-                }
-            }
-        }
-        
-        (best_solution, iterations)
-    }
-    
-    fn tournament_selection<R: Rng>(
-        &self,
-        population: &[(Solution, i32)],
-        tournament_size: usize,
-        rng: &mut R,
-    ) -> usize {
-        let mut best_idx = rng.gen_range(0..population.len());
-        let mut best_cost = population[best_idx].1;
-        
-        for _ in 1..tournament_size {
-            let idx = rng.gen_range(0..population.len());
-            if population[idx].1 < best_cost {
-                best_idx = idx;
-                best_cost = population[idx].1;
-            }
-        }
-        
-        best_idx
+            selection.name(),
+            recombination.name(),
+            replacement.name(),
+            base_local_search.neighborhood
+        );
+
+        let child_improvement: Box<dyn ChildImprovement> = if adaptive_local_search {
+            Box::new(AdaptiveLocalSearch::new(
+                [edge_exchange_ls.clone(), vertex_exchange_ls],
+                NeighborhoodChoice::EpsilonGreedy { epsilon: 0.1 },
+            ))
+        } else {
+            Box::new(edge_exchange_ls.clone())
+        };
+        let engine = SteadyStateEvolution::new(
+            InitialPopulation::new(pop_size, edge_exchange_ls, true),
+            selection,
+            recombination,
+            replacement,
+            Some(child_improvement),
+            Some(PeriodicPerturbation::new(
+                50,
+                Box::new(SmallPerturbation::new(10)),
+                None,
+            )),
+        );
+        Self { engine, name_str }
     }
-    
-    fn advanced_recombine<R: Rng>(
+}
+
+impl BudgetedAlgorithm for EnhancedHae {
+    fn solve_budgeted(
         &self,
-        p1: &Solution,
-        p2: &Solution,
         instance: &TsplibInstance,
-        rng: &mut R,
-    ) -> Solution {
-        let mut child = if p1.calculate_cost(instance) < p2.calculate_cost(instance) {
-            p1.clone()
-        } else {
-            p2.clone()
-        };
-        
-        let mut destroyed: HashSet<usize> = HashSet::new();
-        
-        for &cycle_id in &[CycleId::Cycle1, CycleId::Cycle2] {
-            let cycle = child.get_cycle(cycle_id);
-            let n = cycle.len();
-            for i in 0..n {
-                let a = cycle[i];
-                let b = cycle[(i + 1) % n];
-                
-                if p2.has_edge(a, b).is_none() && p1.has_edge(a, b).is_none() {
-                    destroyed.insert(a);
-                    destroyed.insert(b);
-                } else if rng.gen_bool(0.1) {
-                    destroyed.insert(a);
-                    destroyed.insert(b);
-                }
-            }
-        }
-        
-        let mut node_costs: Vec<(usize, i32)> = Vec::new();
-        for node in 0..instance.dimension {
-            if !destroyed.contains(&node) {
-                let mut total_cost = 0;
-                for other in 0..instance.dimension {
-                    if node != other {
-                        total_cost += instance.distance(node, other);
-                    }
-                }
-                node_costs.push((node, total_cost));
-            }
-        }
-        node_costs.sort_by_key(|(_, cost)| -*cost);
-        
-        let destroy_count = (node_costs.len() as f64 * 0.1).ceil() as usize;
-        for i in 0..destroy_count.min(node_costs.len()) {
-            destroyed.insert(node_costs[i].0);
-        }
-        
-        child.cycle1.retain(|v| !destroyed.contains(v));
-        child.cycle2.retain(|v| !destroyed.contains(v));
-        
-        repair(&mut child, instance, destroyed);
-        
-        child
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        self.engine
+            .solve_budgeted(instance, termination, progress_callback)
     }
 }
 
@@ -311,14 +92,14 @@ impl TspAlgorithm for EnhancedHae {
     fn name(&self) -> &str {
         &self.name_str
     }
-    
+
     fn solve_with_feedback(
         &self,
         instance: &TsplibInstance,
         progress_callback: ProgressCallback,
     ) -> Solution {
         let time_limit = Duration::from_secs(60);
-        let (solution, _) = self.solve_timed(instance, time_limit, progress_callback);
-        solution
+        self.solve_budgeted(instance, &Termination::TimeLimit(time_limit), progress_callback)
+            .solution
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,231 @@
+use crate::algorithm::{ProgressCallback, ProgressEvent};
+use crate::algorithms::local_search::base::LocalSearch;
+use crate::algorithms::perturbation::Perturbation;
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+
+/// What a `ChildImprovement` learns during one run. It is owned by the engine's run,
+/// so one configured improvement can serve several runs at the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ImprovementState {
+    /// Total improvement and number of uses of each alternative the improvement picks from.
+    pub operator_stats: Vec<(i32, usize)>,
+}
+
+/// Improves a recombined child before it is offered to the replacement policy.
+pub trait ChildImprovement: Send + Sync {
+    fn name(&self) -> String;
+    /// State for a new run.
+    fn initial_state(&self) -> ImprovementState {
+        ImprovementState::default()
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn improve(
+        &self,
+        child: Solution,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        iteration: usize,
+        state: &mut ImprovementState,
+        rng: &mut dyn RngCore,
+        progress_callback: ProgressCallback,
+    ) -> Solution;
+    /// Reports what was learned during the run.
+    fn report(&self, _state: &ImprovementState, _progress_callback: ProgressCallback) {}
+}
+
+impl ChildImprovement for LocalSearch {
+    fn name(&self) -> String {
+        format!("LS({:?}, {:?})", self.variant, self.neighborhood)
+    }
+
+    fn improve(
+        &self,
+        child: Solution,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        iteration: usize,
+        _state: &mut ImprovementState,
+        _rng: &mut dyn RngCore,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        self.solve_from_solution_until(instance, child, termination, &mut |event| {
+            progress_callback(event.nested(format!("Iter {} child LS", iteration)))
+        })
+        .solution
+    }
+}
+
+/// How `AdaptiveLocalSearch` picks a neighbourhood once both have been tried.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NeighborhoodChoice {
+    /// The one with the larger average improvement, or a uniformly random one with
+    /// probability `epsilon`.
+    EpsilonGreedy { epsilon: f64 },
+    /// The first one with probability `0.5 * (1 + tanh((avg_0 - avg_1) / scale))`.
+    Tanh { scale: f64 },
+}
+
+/// Improves each child with one of two local searches, preferring the one whose
+/// neighbourhood has improved children the most on average. Every choice is
+/// reported as an `OperatorChosen` event named after the neighbourhood.
+#[derive(Debug, Clone)]
+pub struct AdaptiveLocalSearch {
+    searches: [LocalSearch; 2],
+    choice: NeighborhoodChoice,
+}
+
+impl AdaptiveLocalSearch {
+    pub fn new(searches: [LocalSearch; 2], choice: NeighborhoodChoice) -> Self {
+        Self { searches, choice }
+    }
+
+    /// Index of the local search to use and the probability it had of being chosen.
+    /// Each one is tried once before the averages in `stats` are trusted.
+    fn choose(&self, stats: &[(i32, usize)], rng: &mut dyn RngCore) -> (usize, f64) {
+        if let Some(untried) = stats.iter().position(|&(_, uses)| uses == 0) {
+            return (untried, 1.0);
+        }
+        let avg = |i: usize| stats[i].0 as f64 / stats[i].1 as f64;
+        let (avg_0, avg_1) = (avg(0), avg(1));
+        match self.choice {
+            NeighborhoodChoice::EpsilonGreedy { epsilon } => {
+                let greedy = if avg_0 > avg_1 { 0 } else { 1 };
+                let choice = if rng.random_bool(epsilon) {
+                    rng.random_range(0..2)
+                } else {
+                    greedy
+                };
+                let probability = if choice == greedy {
+                    1.0 - epsilon / 2.0
+                } else {
+                    epsilon / 2.0
+                };
+                (choice, probability)
+            }
+            NeighborhoodChoice::Tanh { scale } => {
+                let prob_0 = 0.5 * (1.0 + ((avg_0 - avg_1) / scale).tanh());
+                if rng.random_bool(prob_0) {
+                    (0, prob_0)
+                } else {
+                    (1, 1.0 - prob_0)
+                }
+            }
+        }
+    }
+}
+
+impl ChildImprovement for AdaptiveLocalSearch {
+    fn name(&self) -> String {
+        format!(
+            "Adaptive({:?}/{:?}, {:?})",
+            self.searches[0].neighborhood, self.searches[1].neighborhood, self.choice
+        )
+    }
+
+    fn initial_state(&self) -> ImprovementState {
+        ImprovementState {
+            operator_stats: vec![(0, 0); self.searches.len()],
+        }
+    }
+
+    fn improve(
+        &self,
+        child: Solution,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        iteration: usize,
+        state: &mut ImprovementState,
+        rng: &mut dyn RngCore,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let (choice, probability) = self.choose(&state.operator_stats, rng);
+        let search = &self.searches[choice];
+        progress_callback(ProgressEvent::OperatorChosen {
+            iteration,
+            operator: format!("{:?}", search.neighborhood),
+            probability,
+        });
+
+        let cost_before = child.calculate_cost(instance);
+        let improved = search
+            .solve_from_solution_until(instance, child, termination, &mut |event| {
+                progress_callback(
+                    event.nested(format!("Iter {} {:?}-LS", iteration, search.neighborhood)),
+                )
+            })
+            .solution;
+        let improvement = cost_before - improved.calculate_cost(instance);
+        let stats = &mut state.operator_stats[choice];
+        stats.0 += improvement;
+        stats.1 += 1;
+        improved
+    }
+
+    fn report(&self, state: &ImprovementState, progress_callback: ProgressCallback) {
+        for (search, &(total, uses)) in self.searches.iter().zip(&state.operator_stats) {
+            if uses > 0 {
+                progress_callback(ProgressEvent::Message(format!(
+                    "[Adaptive LS] Neighborhood {:?}: uses={}, avg_improvement={:.2}",
+                    search.neighborhood,
+                    uses,
+                    total as f64 / uses as f64
+                )));
+            }
+        }
+    }
+}
+
+/// Every `period` iterations perturbs the worst member of the population and, if a
+/// local search is given, brings it back to a local optimum.
+pub struct PeriodicPerturbation {
+    pub period: usize,
+    perturbation: Box<dyn Perturbation>,
+    local_search: Option<LocalSearch>,
+}
+
+impl PeriodicPerturbation {
+    pub fn new(
+        period: usize,
+        perturbation: Box<dyn Perturbation>,
+        local_search: Option<LocalSearch>,
+    ) -> Self {
+        Self {
+            period: period.max(1),
+            perturbation,
+            local_search,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let ls = match &self.local_search {
+            Some(ls) => format!(" + LS({:?})", ls.neighborhood),
+            None => String::new(),
+        };
+        format!("every {}: {}{}", self.period, self.perturbation.name(), ls)
+    }
+
+    /// Replaces the worst member of `population` (sorted by cost) with its perturbed
+    /// copy and re-sorts. Returns the new member and its cost.
+    pub fn apply(
+        &self,
+        population: &mut [(Solution, i32)],
+        instance: &TsplibInstance,
+        termination: &Termination,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Solution, i32)> {
+        let (worst, _) = population.last()?;
+        let mut solution = worst.clone();
+        self.perturbation.perturb(&mut solution, instance, rng);
+        if let Some(local_search) = &self.local_search {
+            solution = local_search
+                .solve_from_solution_until(instance, solution, termination, &mut |_| {})
+                .solution;
+        }
+        let cost = solution.calculate_cost(instance);
+        *population.last_mut()? = (solution.clone(), cost);
+        population.sort_by_key(|(_, cost)| *cost);
+        Some((solution, cost))
+    }
+}
@@ -0,0 +1,285 @@
+//! Steady-state evolutionary algorithm assembled from exchangeable operators.
+//!
+//! Each iteration selects two parents, recombines them, optionally improves the child
+//! and offers it to the replacement policy; an optional periodic perturbation of the
+//! worst member keeps the population moving. The HAE variants are configurations of
+//! this engine, so new combinations only need a new `SteadyStateEvolution`.
+
+pub mod improvement;
+pub mod replacement;
+pub mod selection;
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::{HeuristicAlgorithm, InitialSolutionType, LocalSearch};
+use crate::algorithms::recombination::Recombination;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use improvement::{ChildImprovement, PeriodicPerturbation};
+use rand::rng;
+use replacement::{Replacement, ReplacementState};
+use selection::ParentSelection;
+use std::time::Duration;
+
+/// How the initial population is built: local optima of `local_search` from
+/// weighted-regret starts for a quarter of the population (if `use_heuristic_init`)
+/// and from random starts for the rest.
+#[derive(Debug, Clone)]
+pub struct InitialPopulation {
+    pub size: usize,
+    pub local_search: LocalSearch,
+    pub use_heuristic_init: bool,
+}
+
+impl InitialPopulation {
+    pub fn new(size: usize, local_search: LocalSearch, use_heuristic_init: bool) -> Self {
+        Self {
+            size,
+            local_search,
+            use_heuristic_init,
+        }
+    }
+
+    /// Members sorted by cost.
+    fn generate(
+        &self,
+        instance: &TsplibInstance,
+        ls_termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> Vec<(Solution, i32)> {
+        let num_heuristic = if self.use_heuristic_init {
+            self.size / 4
+        } else {
+            0
+        };
+        let mut population: Vec<(Solution, i32)> = (0..self.size)
+            .map(|i| {
+                let initial_solution_type = if i < num_heuristic {
+                    InitialSolutionType::Heuristic(HeuristicAlgorithm::WeightedRegret)
+                } else {
+                    InitialSolutionType::Random
+                };
+                let init_ls = LocalSearch::new(
+                    self.local_search.variant,
+                    self.local_search.neighborhood,
+                    initial_solution_type,
+                    self.local_search.verify_delta_computationally,
+                );
+                let sol = init_ls
+                    .solve_budgeted(instance, ls_termination, &mut |event| {
+                        progress_callback(event.nested(format!("Init {}", i + 1)))
+                    })
+                    .solution;
+                let cost = sol.calculate_cost(instance);
+                (sol, cost)
+            })
+            .collect();
+        population.sort_by_key(|(_, cost)| *cost);
+        population
+    }
+}
+
+pub struct SteadyStateEvolution {
+    initial_population: InitialPopulation,
+    selection: Box<dyn ParentSelection>,
+    recombination: Box<dyn Recombination>,
+    replacement: Box<dyn Replacement>,
+    /// Applied to every child before replacement; children enter as recombined if `None`.
+    child_improvement: Option<Box<dyn ChildImprovement>>,
+    /// Keeps a converged population moving by periodically perturbing its worst member.
+    perturbation: Option<PeriodicPerturbation>,
+    name_str: String,
+}
+
+impl SteadyStateEvolution {
+    pub fn new(
+        initial_population: InitialPopulation,
+        selection: Box<dyn ParentSelection>,
+        recombination: Box<dyn Recombination>,
+        replacement: Box<dyn Replacement>,
+        child_improvement: Option<Box<dyn ChildImprovement>>,
+        perturbation: Option<PeriodicPerturbation>,
+    ) -> Self {
+        let name_str = format!(
+            "Steady-State EA (pop={}, heur_init={}, select={}, recomb={}, replace={}, child_ls={}, perturb={}, init_ls_var={:?}, init_ls_neigh={:?})",
+            initial_population.size,
+            initial_population.use_heuristic_init,
+            selection.name(),
+            recombination.name(),
+            replacement.name(),
+            child_improvement
+                .as_ref()
+                .map_or("off".to_string(), |improvement| improvement.name()),
+            perturbation
+                .as_ref()
+                .map_or("off".to_string(), |perturbation| perturbation.name()),
+            initial_population.local_search.variant,
+            initial_population.local_search.neighborhood
+        );
+        Self {
+            initial_population,
+            selection,
+            recombination,
+            replacement,
+            child_improvement,
+            perturbation,
+            name_str,
+        }
+    }
+}
+
+impl BudgetedAlgorithm for SteadyStateEvolution {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let mut improvement_state = self
+            .child_improvement
+            .as_ref()
+            .map(|improvement| improvement.initial_state())
+            .unwrap_or_default();
+        let mut replacement_state = ReplacementState::default();
+
+        progress_callback(ProgressEvent::Message(format!(
+            "[{}] Generating initial population...",
+            self.name_str
+        )));
+        let mut population =
+            self.initial_population
+                .generate(instance, &ls_termination, progress_callback);
+        for (_, cost) in &population {
+            progress.record_evaluation(*cost);
+        }
+        let mut best_solution = population[0].0.clone();
+        let mut best_cost = population[0].1;
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let (p1_idx, p2_idx) = self.selection.select(&population, &mut rng);
+            let mut child = self.recombination.recombine(
+                &population[p1_idx].0,
+                &population[p2_idx].0,
+                instance,
+                &ls_termination,
+                &mut rng,
+            );
+            if let Some(improvement) = &self.child_improvement {
+                child = improvement.improve(
+                    child,
+                    instance,
+                    &ls_termination,
+                    iterations,
+                    &mut improvement_state,
+                    &mut rng,
+                    progress_callback,
+                );
+            }
+            let child_cost = child.calculate_cost(instance);
+            progress.record_evaluation(child_cost);
+
+            if child_cost < best_cost {
+                best_solution = child.clone();
+                best_cost = child_cost;
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost: child_cost,
+                best_cost,
+            });
+
+            if let Some(index) = self.replacement.replace(
+                &mut population,
+                child,
+                child_cost,
+                instance,
+                &mut replacement_state,
+                &mut rng,
+            ) {
+                progress_callback(ProgressEvent::PopulationReplaced {
+                    iteration: iterations,
+                    index,
+                    cost: child_cost,
+                });
+            }
+
+            let Some(perturbation) = &self.perturbation else {
+                continue;
+            };
+            if !iterations.is_multiple_of(perturbation.period) {
+                continue;
+            }
+            progress_callback(ProgressEvent::Message(format!(
+                "[{}] Iter {}: Perturbing worst solution...",
+                self.name_str, iterations
+            )));
+            if let Some((solution, cost)) =
+                perturbation.apply(&mut population, instance, &ls_termination, &mut rng)
+            {
+                progress.record_evaluation(cost);
+                if cost < best_cost {
+                    best_solution = solution;
+                    best_cost = cost;
+                    progress_callback(ProgressEvent::NewBest {
+                        iteration: iterations,
+                        cost: best_cost,
+                        elapsed: progress.elapsed(),
+                    });
+                }
+            }
+        };
+
+        if let Some(improvement) = &self.child_improvement {
+            improvement.report(&improvement_state, progress_callback);
+        }
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for SteadyStateEvolution {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -0,0 +1,422 @@
+use crate::global_convexity::calculate_similarity;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+
+/// What a `Replacement` keeps between calls during one run. It is owned by the run,
+/// so one configured policy can serve several runs at the same time.
+#[derive(Debug, Default)]
+pub struct ReplacementState {
+    dissimilarity: DissimilarityMatrix,
+}
+
+/// Decides whether a child enters the population and whom it replaces.
+pub trait Replacement: Send + Sync {
+    fn name(&self) -> String;
+    /// Inserts `child` into `population` (kept sorted by cost) or discards it.
+    /// Returns the index of the replaced member before re-sorting.
+    fn replace(
+        &self,
+        population: &mut Vec<(Solution, i32)>,
+        child: Solution,
+        child_cost: i32,
+        instance: &TsplibInstance,
+        state: &mut ReplacementState,
+        rng: &mut dyn RngCore,
+    ) -> Option<usize>;
+}
+
+/// Scans from the worst member and replaces the first one the child beats by more
+/// than `min_diff`; the worst member only has to be beaten.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplaceFromWorst {
+    pub min_diff: i32,
+}
+
+impl ReplaceFromWorst {
+    pub fn new(min_diff: i32) -> Self {
+        Self { min_diff }
+    }
+}
+
+impl Replacement for ReplaceFromWorst {
+    fn name(&self) -> String {
+        format!("FromWorst(min_diff={})", self.min_diff)
+    }
+
+    fn replace(
+        &self,
+        population: &mut Vec<(Solution, i32)>,
+        child: Solution,
+        child_cost: i32,
+        _instance: &TsplibInstance,
+        _state: &mut ReplacementState,
+        _rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let worst_idx = population.len() - 1;
+        let replaced_idx = (0..population.len()).rev().find(|&i| {
+            child_cost < population[i].1
+                && ((child_cost - population[i].1).abs() > self.min_diff || i == worst_idx)
+        })?;
+        population[replaced_idx] = (child, child_cost);
+        population.sort_by_key(|(_, cost)| *cost);
+        Some(replaced_idx)
+    }
+}
+
+/// Replaces the worst member unless the child's cost is within `min_diff` of one of
+/// the `elite_size` best; a child that still beats the median replaces a random member
+/// of the worse half instead.
+#[derive(Debug, Clone, Copy)]
+pub struct EliteAwareReplacement {
+    pub elite_size: usize,
+    pub min_diff: i32,
+}
+
+impl EliteAwareReplacement {
+    pub fn new(elite_size: usize, min_diff: i32) -> Self {
+        Self {
+            elite_size,
+            min_diff,
+        }
+    }
+}
+
+impl Replacement for EliteAwareReplacement {
+    fn name(&self) -> String {
+        format!(
+            "EliteAware(elite={}, min_diff={})",
+            self.elite_size, self.min_diff
+        )
+    }
+
+    fn replace(
+        &self,
+        population: &mut Vec<(Solution, i32)>,
+        child: Solution,
+        child_cost: i32,
+        _instance: &TsplibInstance,
+        _state: &mut ReplacementState,
+        rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let too_similar = population
+            .iter()
+            .take(self.elite_size)
+            .any(|(_, cost)| (child_cost - *cost).abs() < self.min_diff);
+
+        let replaced_idx = if !too_similar && child_cost < population.last()?.1 {
+            population.len() - 1
+        } else if child_cost < population[population.len() / 2].1 {
+            rng.random_range(population.len() / 2..population.len())
+        } else {
+            return None;
+        };
+        population[replaced_idx] = (child, child_cost);
+        population.sort_by_key(|(_, cost)| *cost);
+        Some(replaced_idx)
+    }
+}
+
+/// Structural similarity used by the diversity-aware replacement policies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityMeasure {
+    /// Share of edges present in both solutions.
+    CommonEdges,
+    /// Share of node pairs kept in one cycle by both solutions, relative to the pairs
+    /// kept together by the first one (so identical solutions score 1.0).
+    SameCyclePairs,
+}
+
+impl SimilarityMeasure {
+    pub fn similarity(self, a: &Solution, b: &Solution, instance: &TsplibInstance) -> f64 {
+        let measures = calculate_similarity(a, b, instance);
+        match self {
+            SimilarityMeasure::CommonEdges => measures.common_edges,
+            SimilarityMeasure::SameCyclePairs => {
+                let n = instance.size();
+                let pairs = |k: usize| (k * k.saturating_sub(1) / 2) as f64;
+                let self_share = (pairs(a.cycle1.len()) + pairs(a.cycle2.len())) / pairs(n);
+                if self_share > 0.0 {
+                    measures.vertex_pairs_same_cycle / self_share
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Index and similarity of the member most similar to `child`.
+fn most_similar(
+    population: &[(Solution, i32)],
+    child: &Solution,
+    measure: SimilarityMeasure,
+    instance: &TsplibInstance,
+) -> Option<(usize, f64)> {
+    population
+        .iter()
+        .map(|(member, _)| measure.similarity(child, member, instance))
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// A child at least `max_similarity` similar to a member is a near-duplicate and may
+/// only replace that member, if it is cheaper. Other children replace the worst member
+/// when they beat it.
+#[derive(Debug, Clone, Copy)]
+pub struct SimilarityReplacement {
+    pub measure: SimilarityMeasure,
+    pub max_similarity: f64,
+}
+
+impl SimilarityReplacement {
+    pub fn new(measure: SimilarityMeasure, max_similarity: f64) -> Self {
+        Self {
+            measure,
+            max_similarity,
+        }
+    }
+}
+
+impl Replacement for SimilarityReplacement {
+    fn name(&self) -> String {
+        format!("Similarity({:?} < {})", self.measure, self.max_similarity)
+    }
+
+    fn replace(
+        &self,
+        population: &mut Vec<(Solution, i32)>,
+        child: Solution,
+        child_cost: i32,
+        instance: &TsplibInstance,
+        _state: &mut ReplacementState,
+        _rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let replaced_idx = match most_similar(population, &child, self.measure, instance)? {
+            (twin, similarity) if similarity >= self.max_similarity => twin,
+            _ => population.len() - 1,
+        };
+        replace_if_cheaper(population, replaced_idx, child, child_cost)
+    }
+}
+
+/// Puts `child` in place of `population[index]` if it is cheaper and re-sorts.
+fn replace_if_cheaper(
+    population: &mut [(Solution, i32)],
+    index: usize,
+    child: Solution,
+    child_cost: i32,
+) -> Option<usize> {
+    if child_cost >= population[index].1 {
+        return None;
+    }
+    population[index] = (child, child_cost);
+    population.sort_by_key(|(_, cost)| *cost);
+    Some(index)
+}
+
+/// Pairwise dissimilarities (`1 - similarity`) of population members, kept between
+/// replacements so that only the rows of new members are computed.
+#[derive(Debug, Default)]
+struct DissimilarityMatrix {
+    /// Member of every row, in population order as of the last update.
+    members: Vec<Solution>,
+    rows: Vec<Vec<f64>>,
+}
+
+impl DissimilarityMatrix {
+    /// Re-aligns the rows with `population`, which may have been re-sorted or changed by
+    /// the caller since the last update; rows are computed only for unknown members.
+    fn sync(
+        &mut self,
+        population: &[(Solution, i32)],
+        measure: SimilarityMeasure,
+        instance: &TsplibInstance,
+    ) {
+        let mut previous: Vec<Option<Solution>> = std::mem::take(&mut self.members)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut members = Vec::with_capacity(population.len());
+        let mut old_index = Vec::with_capacity(population.len());
+        for (member, _) in population {
+            let found = previous.iter().position(|cached| {
+                cached.as_ref().is_some_and(|cached| {
+                    cached.cycle1 == member.cycle1 && cached.cycle2 == member.cycle2
+                })
+            });
+            members.push(match found {
+                Some(k) => previous[k].take().expect("matched at most once"),
+                None => member.clone(),
+            });
+            old_index.push(found);
+        }
+
+        let size = population.len();
+        let mut rows = vec![vec![0.0; size]; size];
+        for i in 0..size {
+            for j in i + 1..size {
+                let d = match (old_index[i], old_index[j]) {
+                    (Some(a), Some(b)) => self.rows[a][b],
+                    _ => 1.0 - measure.similarity(&members[i], &members[j], instance),
+                };
+                rows[i][j] = d;
+                rows[j][i] = d;
+            }
+        }
+        self.members = members;
+        self.rows = rows;
+    }
+
+    /// Dissimilarity of `child` to every member.
+    fn row_of(
+        &self,
+        child: &Solution,
+        measure: SimilarityMeasure,
+        instance: &TsplibInstance,
+    ) -> Vec<f64> {
+        self.members
+            .iter()
+            .map(|member| 1.0 - measure.similarity(child, member, instance))
+            .collect()
+    }
+
+    fn insert(&mut self, index: usize, member: Solution, mut row: Vec<f64>) {
+        for (other, &d) in self.rows.iter_mut().zip(&row) {
+            other.insert(index, d);
+        }
+        row.insert(index, 0.0);
+        self.rows.insert(index, row);
+        self.members.insert(index, member);
+    }
+
+    fn set(&mut self, index: usize, member: Solution, mut row: Vec<f64>) {
+        for (other, &d) in self.rows.iter_mut().zip(&row) {
+            other[index] = d;
+        }
+        row[index] = 0.0;
+        self.rows[index] = row;
+        self.members[index] = member;
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.rows.remove(index);
+        for row in &mut self.rows {
+            row.remove(index);
+        }
+        self.members.remove(index);
+    }
+}
+
+/// Hybrid genetic search survivor selection. Near-duplicates are handled as in
+/// `SimilarityReplacement`; any other child joins the population and the member with
+/// the worst biased fitness leaves. Biased fitness adds the rank of a member's
+/// diversity contribution (mean dissimilarity to its `num_closest` most similar
+/// members), weighted by `1 - num_elite / population size`, to its cost rank, so the
+/// `num_elite` best members survive regardless of diversity.
+#[derive(Debug, Clone, Copy)]
+pub struct BiasedFitnessReplacement {
+    pub measure: SimilarityMeasure,
+    pub max_similarity: f64,
+    pub num_closest: usize,
+    pub num_elite: usize,
+}
+
+impl BiasedFitnessReplacement {
+    pub fn new(
+        measure: SimilarityMeasure,
+        max_similarity: f64,
+        num_closest: usize,
+        num_elite: usize,
+    ) -> Self {
+        Self {
+            measure,
+            max_similarity,
+            num_closest,
+            num_elite,
+        }
+    }
+
+    /// Biased fitness of every member of a population sorted by cost, given the members'
+    /// pairwise dissimilarities; lower is better.
+    fn biased_fitness(&self, dissimilarity: &[Vec<f64>]) -> Vec<f64> {
+        let size = dissimilarity.len();
+        if size < 2 {
+            return vec![0.0; size];
+        }
+        let contribution: Vec<f64> = (0..size)
+            .map(|i| {
+                let mut others: Vec<f64> = (0..size)
+                    .filter(|&j| j != i)
+                    .map(|j| dissimilarity[i][j])
+                    .collect();
+                others.sort_by(f64::total_cmp);
+                let closest = self.num_closest.clamp(1, others.len());
+                others[..closest].iter().sum::<f64>() / closest as f64
+            })
+            .collect();
+
+        // Rank 0 for the largest diversity contribution
+        let mut by_contribution: Vec<usize> = (0..size).collect();
+        by_contribution.sort_by(|&a, &b| contribution[b].total_cmp(&contribution[a]));
+        let mut diversity_rank = vec![0; size];
+        for (rank, &i) in by_contribution.iter().enumerate() {
+            diversity_rank[i] = rank;
+        }
+
+        let scale = (size - 1) as f64;
+        let diversity_weight = 1.0 - self.num_elite as f64 / size as f64;
+        (0..size)
+            .map(|i| i as f64 / scale + diversity_weight * diversity_rank[i] as f64 / scale)
+            .collect()
+    }
+}
+
+impl Replacement for BiasedFitnessReplacement {
+    fn name(&self) -> String {
+        format!(
+            "BiasedFitness({:?} < {}, closest={}, elite={})",
+            self.measure, self.max_similarity, self.num_closest, self.num_elite
+        )
+    }
+
+    fn replace(
+        &self,
+        population: &mut Vec<(Solution, i32)>,
+        child: Solution,
+        child_cost: i32,
+        instance: &TsplibInstance,
+        state: &mut ReplacementState,
+        _rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let matrix = &mut state.dissimilarity;
+        matrix.sync(population, self.measure, instance);
+        let row = matrix.row_of(&child, self.measure, instance);
+
+        // Near-duplicates may only replace their twin; re-sorting the population is
+        // picked up by the next `sync`
+        if let Some(twin) = (0..row.len()).min_by(|&a, &b| row[a].total_cmp(&row[b]))
+            && 1.0 - row[twin] >= self.max_similarity
+        {
+            if child_cost < population[twin].1 {
+                matrix.set(twin, child.clone(), row);
+            }
+            return replace_if_cheaper(population, twin, child, child_cost);
+        }
+
+        // Members keep their indices; the child is ranked among them by cost
+        let child_idx = population.partition_point(|(_, cost)| *cost <= child_cost);
+        matrix.insert(child_idx, child.clone(), row);
+        population.insert(child_idx, (child, child_cost));
+        let fitness = self.biased_fitness(&matrix.rows);
+        let removed = (0..population.len())
+            .max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+            .unwrap_or(child_idx);
+        matrix.remove(removed);
+        population.remove(removed);
+        match removed.cmp(&child_idx) {
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Less => Some(removed),
+            std::cmp::Ordering::Greater => Some(removed - 1),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::tsplib::Solution;
+use rand::{Rng, RngCore};
+
+/// Chooses the indices of two parents in a population sorted by cost.
+pub trait ParentSelection: Send + Sync {
+    fn name(&self) -> String;
+    fn select(&self, population: &[(Solution, i32)], rng: &mut dyn RngCore) -> (usize, usize);
+}
+
+/// Two distinct members drawn uniformly at random.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformSelection;
+
+impl ParentSelection for UniformSelection {
+    fn name(&self) -> String {
+        "Uniform".to_string()
+    }
+
+    fn select(&self, population: &[(Solution, i32)], rng: &mut dyn RngCore) -> (usize, usize) {
+        let p1_idx = rng.random_range(0..population.len());
+        let mut p2_idx = rng.random_range(0..population.len());
+        while p1_idx == p2_idx && population.len() > 1 {
+            p2_idx = rng.random_range(0..population.len());
+        }
+        (p1_idx, p2_idx)
+    }
+}
+
+/// Each parent is the cheapest of `size` members drawn with replacement; both
+/// tournaments are independent, so the parents may coincide.
+#[derive(Debug, Clone, Copy)]
+pub struct TournamentSelection {
+    pub size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+
+    fn tournament(&self, population: &[(Solution, i32)], rng: &mut dyn RngCore) -> usize {
+        let mut best_idx = rng.random_range(0..population.len());
+        let mut best_cost = population[best_idx].1;
+
+        for _ in 1..self.size {
+            let idx = rng.random_range(0..population.len());
+            if population[idx].1 < best_cost {
+                best_idx = idx;
+                best_cost = population[idx].1;
+            }
+        }
+
+        best_idx
+    }
+}
+
+impl ParentSelection for TournamentSelection {
+    fn name(&self) -> String {
+        format!("Tournament({})", self.size)
+    }
+
+    fn select(&self, population: &[(Solution, i32)], rng: &mut dyn RngCore) -> (usize, usize) {
+        (
+            self.tournament(population, rng),
+            self.tournament(population, rng),
+        )
+    }
+}
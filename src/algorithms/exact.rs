@@ -0,0 +1,480 @@
+//! Exact solver for small instances.
+//!
+//! Up to `HELD_KARP_MAX_NODES` nodes the optimum is found by a Held-Karp dynamic program:
+//! the optimal cycle over every subset is computed once, and the best split into two
+//! balanced, complementary subsets is picked. Larger instances are solved by depth-first
+//! branch-and-bound that grows the cycle containing node 0 and then the other one. Its
+//! bound charges every unvisited node half of its two cheapest edges and every open path
+//! end half of its cheapest edge; the search starts from a local search upper bound. On
+//! random Euclidean instances it proves optimality for about two dozen nodes in seconds.
+//!
+//! A run that ends with `StopReason::Converged` has proven its solution optimal. If the
+//! termination criterion fires first, the best solution found so far is returned.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::{
+    HeuristicAlgorithm, InitialSolutionType, LocalSearch, NeighborhoodType, SearchVariant,
+};
+use crate::termination::{SearchProgress, StopReason, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+
+/// Largest instance solved with the Held-Karp program (`2^n * n` table entries).
+const HELD_KARP_MAX_NODES: usize = 16;
+/// Branch-and-bound nodes between termination checks.
+const CHECK_INTERVAL: usize = 4096;
+
+pub struct ExactSolver {
+    name_str: String,
+}
+
+impl ExactSolver {
+    pub fn new() -> Self {
+        Self {
+            name_str: "Exact (Held-Karp / Branch-and-Bound)".to_string(),
+        }
+    }
+
+    /// Branch-and-bound from a local search upper bound; used above `HELD_KARP_MAX_NODES`.
+    fn branch_and_bound(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let n = instance.size();
+        let upper_bound_search = LocalSearch::new(
+            SearchVariant::Steepest,
+            NeighborhoodType::EdgeExchange,
+            InitialSolutionType::Heuristic(HeuristicAlgorithm::WeightedRegret),
+            false,
+        );
+        let initial = upper_bound_search
+            .solve_budgeted(instance, &termination.cancellation_only(), &mut |event| {
+                progress_callback(event.nested("Upper bound LS"))
+            })
+            .solution;
+        let initial_cost = initial.calculate_cost(instance);
+        progress_callback(ProgressEvent::Message(format!(
+            "[{}] Branch-and-bound on {} nodes, initial upper bound {}",
+            self.name_str, n, initial_cost
+        )));
+
+        // Node 0 may lie in either cycle when their sizes differ
+        let mut search = BranchAndBound::new(instance, termination, initial_cost);
+        search.search(n.div_ceil(2), progress_callback);
+        if n % 2 == 1 && search.stop.is_none() {
+            search.search(n / 2, progress_callback);
+        }
+
+        let stop_reason = search.stop.unwrap_or(StopReason::Converged);
+        let solution = match search.best {
+            Some([with_zero, other]) if with_zero.len() >= other.len() => {
+                Solution::new(with_zero, other)
+            }
+            Some([with_zero, other]) => Solution::new(other, with_zero),
+            None => initial,
+        };
+        progress_callback(ProgressEvent::Finished {
+            iterations: search.progress.iterations,
+            best_cost: search.best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution,
+            iterations: search.progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl Default for ExactSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Optimal solution by dynamic programming over subsets; requires `n <= HELD_KARP_MAX_NODES`.
+fn held_karp(instance: &TsplibInstance) -> Solution {
+    let n = instance.size();
+    if n == 0 {
+        return Solution::new(vec![], vec![]);
+    }
+    let full = 1usize << n;
+    // paths[mask * n + j]: shortest path that starts at the lowest node of `mask`, visits
+    // every node of `mask` and ends at `j`
+    let mut paths = vec![i32::MAX; full * n];
+    for start in 0..n {
+        paths[(1 << start) * n + start] = 0;
+    }
+    for mask in 1..full {
+        let low = mask.trailing_zeros() as usize;
+        for j in 0..n {
+            let cost = paths[mask * n + j];
+            if cost == i32::MAX {
+                continue;
+            }
+            for k in low + 1..n {
+                if mask & (1 << k) != 0 {
+                    continue;
+                }
+                let next = (mask | (1 << k)) * n + k;
+                let candidate = cost + instance.distance(j, k);
+                if candidate < paths[next] {
+                    paths[next] = candidate;
+                }
+            }
+        }
+    }
+
+    let closing_end = |mask: usize| -> (i32, usize) {
+        let low = mask.trailing_zeros() as usize;
+        (0..n)
+            .filter(|&j| mask & (1 << j) != 0 && paths[mask * n + j] != i32::MAX)
+            .map(|j| (paths[mask * n + j] + instance.distance(j, low), j))
+            .min()
+            .expect("every non-empty subset has a path")
+    };
+    let cycle_cost = |mask: usize| if mask == 0 { 0 } else { closing_end(mask).0 };
+
+    let size1 = n.div_ceil(2);
+    let all = full - 1;
+    let mask1 = (0..full)
+        .filter(|mask| mask.count_ones() as usize == size1)
+        .min_by_key(|&mask| cycle_cost(mask) + cycle_cost(all ^ mask))
+        .expect("a balanced split exists");
+
+    let tour = |mask: usize| -> Vec<usize> {
+        if mask == 0 {
+            return Vec::new();
+        }
+        let (_, mut current) = closing_end(mask);
+        let mut remaining = mask;
+        let mut reversed = vec![current];
+        while remaining.count_ones() > 1 {
+            let rest = remaining ^ (1 << current);
+            let target = paths[remaining * n + current];
+            let previous = (0..n)
+                .find(|&i| {
+                    rest & (1 << i) != 0
+                        && paths[rest * n + i] != i32::MAX
+                        && paths[rest * n + i] + instance.distance(i, current) == target
+                })
+                .expect("the DP table is consistent");
+            reversed.push(previous);
+            remaining = rest;
+            current = previous;
+        }
+        reversed.reverse();
+        reversed
+    };
+    Solution::new(tour(mask1), tour(all ^ mask1))
+}
+
+/// Depth-first branch-and-bound state.
+struct BranchAndBound<'a> {
+    instance: &'a TsplibInstance,
+    /// Target sizes of the cycle containing node 0 and of the other cycle.
+    sizes: [usize; 2],
+    /// Every node's neighbours ordered by distance, so good completions are tried first.
+    neighbors: Vec<Vec<usize>>,
+    /// Cheapest edge of every node.
+    min_edge: Vec<i32>,
+    /// Sum of the two cheapest edges of every node.
+    min_two_edges: Vec<i32>,
+    cycles: [Vec<usize>; 2],
+    visited: Vec<bool>,
+    cost: i32,
+    /// Sum of `min_two_edges` over the unvisited nodes.
+    unvisited_bound: i32,
+    best_cost: i32,
+    best: Option<[Vec<usize>; 2]>,
+    progress: SearchProgress,
+    termination: &'a Termination,
+    stop: Option<StopReason>,
+}
+
+impl<'a> BranchAndBound<'a> {
+    fn new(instance: &'a TsplibInstance, termination: &'a Termination, upper_bound: i32) -> Self {
+        let n = instance.size();
+        let neighbors: Vec<Vec<usize>> = (0..n)
+            .map(|u| {
+                let mut others: Vec<usize> = (0..n).filter(|&v| v != u).collect();
+                others.sort_by_key(|&v| instance.distance(u, v));
+                others
+            })
+            .collect();
+        let min_edge: Vec<i32> = (0..n)
+            .map(|u| instance.distance(u, neighbors[u][0]))
+            .collect();
+        let min_two_edges: Vec<i32> = (0..n)
+            .map(|u| min_edge[u] + instance.distance(u, neighbors[u][1]))
+            .collect();
+        Self {
+            instance,
+            sizes: [n.div_ceil(2), n / 2],
+            neighbors,
+            min_edge,
+            min_two_edges,
+            cycles: [Vec::new(), Vec::new()],
+            visited: vec![false; n],
+            cost: 0,
+            unvisited_bound: 0,
+            best_cost: upper_bound,
+            best: None,
+            progress: SearchProgress::new(),
+            termination,
+            stop: None,
+        }
+    }
+
+    /// Explores all solutions where the cycle containing node 0 has `size0` nodes.
+    fn search(&mut self, size0: usize, progress_callback: ProgressCallback) {
+        let n = self.instance.size();
+        self.sizes = [size0, n - size0];
+        self.visited.fill(false);
+        self.cycles = [vec![0], Vec::new()];
+        self.visited[0] = true;
+        self.cost = 0;
+        self.unvisited_bound = self.min_two_edges[1..].iter().sum();
+        self.extend(0, progress_callback);
+    }
+
+    fn visit(&mut self, c: usize, v: usize, edge: i32) {
+        self.cycles[c].push(v);
+        self.visited[v] = true;
+        self.cost += edge;
+        self.unvisited_bound -= self.min_two_edges[v];
+    }
+
+    fn unvisit(&mut self, c: usize, v: usize, edge: i32) {
+        self.cycles[c].pop();
+        self.visited[v] = false;
+        self.cost -= edge;
+        self.unvisited_bound += self.min_two_edges[v];
+    }
+
+    fn extend(&mut self, c: usize, progress_callback: ProgressCallback) {
+        if self.stop.is_some() {
+            return;
+        }
+        self.progress.iterations += 1;
+        if self.progress.iterations.is_multiple_of(CHECK_INTERVAL) {
+            self.stop = self.termination.check(&self.progress);
+            if self.stop.is_some() {
+                return;
+            }
+        }
+
+        let cycle = &self.cycles[c];
+        let (first, last) = (cycle[0], cycle[cycle.len() - 1]);
+        if cycle.len() == self.sizes[c] {
+            let closing = if cycle.len() > 1 {
+                self.instance.distance(last, first)
+            } else {
+                0
+            };
+            if c == 0 && self.sizes[1] > 0 {
+                // The second cycle holds all remaining nodes, so its start is arbitrary
+                let start = self
+                    .visited
+                    .iter()
+                    .position(|&v| !v)
+                    .expect("the second cycle has unvisited nodes");
+                self.visit(1, start, closing);
+                self.extend(1, progress_callback);
+                self.unvisit(1, start, closing);
+                return;
+            }
+            let total = self.cost + closing;
+            self.progress.record_evaluation(total);
+            if total < self.best_cost {
+                self.best_cost = total;
+                self.best = Some(self.cycles.clone());
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: self.progress.iterations,
+                    cost: total,
+                    elapsed: self.progress.elapsed(),
+                });
+            }
+            return;
+        }
+
+        let closes_cycle = cycle.len() + 1 == self.sizes[c];
+        // Each cycle is explored in one direction only: its last node follows its second
+        let second = cycle.get(1).copied();
+        for index in 0..self.neighbors[last].len() {
+            let v = self.neighbors[last][index];
+            if self.visited[v] || (closes_cycle && second.is_some_and(|s| v < s)) {
+                continue;
+            }
+            let edge = self.instance.distance(last, v);
+            let remaining = self.unvisited_bound - self.min_two_edges[v];
+            let doubled_bound = if closes_cycle {
+                2 * (self.cost + edge + self.instance.distance(v, first)) + remaining
+            } else {
+                2 * (self.cost + edge) + remaining + self.min_edge[first] + self.min_edge[v]
+            };
+            if (doubled_bound + 1) / 2 >= self.best_cost {
+                continue;
+            }
+            self.visit(c, v, edge);
+            self.extend(c, progress_callback);
+            self.unvisit(c, v, edge);
+            if self.stop.is_some() {
+                return;
+            }
+        }
+    }
+}
+
+impl BudgetedAlgorithm for ExactSolver {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let n = instance.size();
+        if n <= HELD_KARP_MAX_NODES {
+            progress_callback(ProgressEvent::Message(format!(
+                "[{}] Held-Karp on {} nodes",
+                self.name_str, n
+            )));
+            let solution = held_karp(instance);
+            progress_callback(ProgressEvent::Finished {
+                iterations: 1,
+                best_cost: solution.calculate_cost(instance),
+                stop_reason: StopReason::Converged,
+            });
+            return SolveOutcome {
+                solution,
+                iterations: 1,
+                stop_reason: StopReason::Converged,
+            };
+        }
+        self.branch_and_bound(instance, termination, progress_callback)
+    }
+}
+
+impl TspAlgorithm for ExactSolver {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        // No budget: run until optimality is proven
+        self.solve_budgeted(instance, &Termination::Any(Vec::new()), progress_callback)
+            .solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_instance(n: usize, rng: &mut StdRng) -> TsplibInstance {
+        let coordinates = (0..n)
+            .map(|_| (rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect();
+        TsplibInstance::from_coordinates("random", coordinates)
+    }
+
+    /// Cheapest cycle through `nodes` by trying every order that starts at the first node.
+    fn brute_force_cycle(instance: &TsplibInstance, nodes: &mut [usize], fixed: usize) -> i32 {
+        if fixed == nodes.len() {
+            return Solution::new(nodes.to_vec(), Vec::new()).calculate_cost(instance);
+        }
+        let mut best = i32::MAX;
+        for i in fixed..nodes.len() {
+            nodes.swap(fixed, i);
+            best = best.min(brute_force_cycle(instance, nodes, fixed + 1));
+            nodes.swap(fixed, i);
+        }
+        best
+    }
+
+    /// Optimum over every balanced split and every pair of cycle orders.
+    fn brute_force_optimum(instance: &TsplibInstance) -> i32 {
+        let n = instance.size();
+        (0..1usize << n)
+            .filter(|mask| mask.count_ones() as usize == n.div_ceil(2))
+            .map(|mask| {
+                let (mut first, mut second): (Vec<usize>, Vec<usize>) =
+                    (0..n).partition(|&v| mask & (1 << v) != 0);
+                brute_force_cycle(instance, &mut first, 1)
+                    + brute_force_cycle(instance, &mut second, 1)
+            })
+            .min()
+            .expect("a balanced split exists")
+    }
+
+    fn assert_balanced(solution: &Solution, instance: &TsplibInstance) {
+        let n = instance.size();
+        assert!(solution.is_valid(instance));
+        assert_eq!(solution.cycle1.len(), n.div_ceil(2));
+        assert_eq!(solution.cycle2.len(), n / 2);
+    }
+
+    #[test]
+    fn held_karp_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(47);
+        for n in 6..=10 {
+            let instance = random_instance(n, &mut rng);
+            let outcome = ExactSolver::new().solve_budgeted(
+                &instance,
+                &Termination::Any(Vec::new()),
+                &mut |_| {},
+            );
+            assert_eq!(outcome.stop_reason, StopReason::Converged);
+            assert_balanced(&outcome.solution, &instance);
+            assert_eq!(
+                outcome.solution.calculate_cost(&instance),
+                brute_force_optimum(&instance)
+            );
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(48);
+        for n in 6..=10 {
+            let instance = random_instance(n, &mut rng);
+            let outcome = ExactSolver::new().branch_and_bound(
+                &instance,
+                &Termination::Any(Vec::new()),
+                &mut |_| {},
+            );
+            assert_eq!(outcome.stop_reason, StopReason::Converged);
+            assert_balanced(&outcome.solution, &instance);
+            assert_eq!(
+                outcome.solution.calculate_cost(&instance),
+                brute_force_optimum(&instance)
+            );
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_matches_held_karp_beyond_its_limit() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let instance = random_instance(HELD_KARP_MAX_NODES + 1, &mut rng);
+        let outcome = ExactSolver::new().solve_budgeted(
+            &instance,
+            &Termination::Any(Vec::new()),
+            &mut |_| {},
+        );
+        assert_eq!(outcome.stop_reason, StopReason::Converged);
+        assert_balanced(&outcome.solution, &instance);
+        assert_eq!(
+            outcome.solution.calculate_cost(&instance),
+            held_karp(&instance).calculate_cost(&instance)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Greedy randomized adaptive search procedure.
+//!
+//! Every iteration builds a solution with the weighted regret heuristic, drawing each
+//! insertion uniformly from a restricted candidate list, and improves it with local search.
+//! In reactive mode the RCL parameter `alpha` is drawn from a fixed set of values with
+//! probabilities proportional to the quality of the solutions each value produced.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::constructive::weighted_regret_cycle::WeightedRegretCycle;
+use crate::algorithms::local_search::base::LocalSearch;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+use std::time::Duration;
+
+/// Exponent amplifying the differences between the reactive `alpha` scores.
+const REACTIVE_AMPLIFICATION: i32 = 10;
+
+/// How the restricted candidate list parameter is chosen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RclAlpha {
+    /// The same `alpha` in every construction.
+    Fixed(f64),
+    /// Reactive GRASP: `alpha` is drawn from `values`; every `update_period` iterations the
+    /// probability of each value is set proportional to `(best / mean_i)^10`, where `mean_i`
+    /// is the mean cost of the local optima reached with that value.
+    Reactive {
+        values: Vec<f64>,
+        update_period: usize,
+    },
+}
+
+/// Probabilities of the reactive `alpha` values and the costs they produced so far.
+struct ReactiveAlpha {
+    values: Vec<f64>,
+    probabilities: Vec<f64>,
+    cost_sums: Vec<f64>,
+    uses: Vec<usize>,
+}
+
+impl ReactiveAlpha {
+    fn new(values: Vec<f64>) -> Self {
+        let count = values.len();
+        Self {
+            values,
+            probabilities: vec![1.0 / count as f64; count],
+            cost_sums: vec![0.0; count],
+            uses: vec![0; count],
+        }
+    }
+
+    /// Draws a value; returns its index and selection probability.
+    fn select<R: Rng + ?Sized>(&self, rng: &mut R) -> (usize, f64) {
+        let mut threshold = rng.random_range(0.0..1.0);
+        let mut chosen = self.values.len() - 1;
+        for (index, &probability) in self.probabilities.iter().enumerate() {
+            if threshold < probability {
+                chosen = index;
+                break;
+            }
+            threshold -= probability;
+        }
+        (chosen, self.probabilities[chosen])
+    }
+
+    fn record(&mut self, index: usize, cost: i32) {
+        self.cost_sums[index] += cost as f64;
+        self.uses[index] += 1;
+    }
+
+    fn update(&mut self, best_cost: i32) {
+        let scores: Vec<f64> = self
+            .cost_sums
+            .iter()
+            .zip(&self.uses)
+            .map(|(&sum, &uses)| {
+                if uses == 0 {
+                    // Unused values keep the highest score, so they still get tried
+                    1.0
+                } else {
+                    (best_cost as f64 / (sum / uses as f64)).powi(REACTIVE_AMPLIFICATION)
+                }
+            })
+            .collect();
+        let total: f64 = scores.iter().sum();
+        for (probability, score) in self.probabilities.iter_mut().zip(scores) {
+            *probability = score / total;
+        }
+    }
+}
+
+pub struct Grasp {
+    construction: WeightedRegretCycle,
+    local_search: LocalSearch,
+    alpha: RclAlpha,
+    name_str: String,
+}
+
+impl Grasp {
+    pub fn new(local_search: LocalSearch, alpha: RclAlpha) -> Self {
+        match &alpha {
+            RclAlpha::Fixed(value) => assert!(
+                (0.0..=1.0).contains(value),
+                "RCL alpha must be within [0, 1]"
+            ),
+            RclAlpha::Reactive {
+                values,
+                update_period,
+            } => assert!(
+                !values.is_empty()
+                    && values.iter().all(|v| (0.0..=1.0).contains(v))
+                    && *update_period > 0,
+                "Reactive GRASP needs alpha values within [0, 1] and a positive update period"
+            ),
+        }
+        let alpha_info = match &alpha {
+            RclAlpha::Fixed(value) => format!("alpha={}", value),
+            RclAlpha::Reactive {
+                values,
+                update_period,
+            } => format!("reactive alpha={:?}, period={}", values, update_period),
+        };
+        let name_str = format!(
+            "GRASP ({}, ls_var={:?}, ls_neigh={:?})",
+            alpha_info, local_search.variant, local_search.neighborhood
+        );
+        Self {
+            construction: WeightedRegretCycle::default(),
+            local_search,
+            alpha,
+            name_str,
+        }
+    }
+}
+
+impl BudgetedAlgorithm for Grasp {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        // A fixed alpha is a single value that is never re-weighted
+        let mut alphas = match &self.alpha {
+            RclAlpha::Fixed(value) => ReactiveAlpha::new(vec![*value]),
+            RclAlpha::Reactive { values, .. } => ReactiveAlpha::new(values.clone()),
+        };
+        let mut best_solution: Option<Solution> = None;
+        let mut best_cost = i32::MAX;
+
+        // At least one construction is always run, so there is a solution to return
+        let stop_reason = loop {
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let (alpha_index, probability) = alphas.select(&mut rng);
+            let alpha = alphas.values[alpha_index];
+            progress_callback(ProgressEvent::OperatorChosen {
+                iteration: iterations,
+                operator: format!("alpha={}", alpha),
+                probability,
+            });
+
+            let constructed = self
+                .construction
+                .construct_randomized(instance, alpha, &mut rng);
+            let current_solution = self
+                .local_search
+                .solve_from_solution_until(instance, constructed, &ls_termination, &mut |event| {
+                    progress_callback(event.nested(format!("Iter {} LS", iterations)))
+                })
+                .solution;
+            let current_cost = current_solution.calculate_cost(instance);
+            progress.record_evaluation(current_cost);
+
+            if current_cost < best_cost {
+                best_cost = current_cost;
+                best_solution = Some(current_solution);
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+            alphas.record(alpha_index, current_cost);
+            if let RclAlpha::Reactive { update_period, .. } = self.alpha
+                && iterations.is_multiple_of(update_period)
+            {
+                alphas.update(best_cost);
+            }
+
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution.expect("GRASP runs at least one iteration"),
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for Grasp {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
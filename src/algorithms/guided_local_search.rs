@@ -0,0 +1,193 @@
+//! Guided local search with edge penalties.
+//!
+//! Local search runs on the augmented cost `distance + lambda * penalty`. After each local
+//! optimum the edges with the highest utility `distance / (1 + penalty)` are penalized, so
+//! the next descent is pushed away from the features the search keeps returning to.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::{LocalSearch, generate_initial_solution};
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{EdgeCost, Solution, TsplibInstance};
+use std::time::Duration;
+
+/// Instance distances plus `lambda` times the accumulated penalty of each edge.
+struct PenalizedCosts<'a> {
+    instance: &'a TsplibInstance,
+    penalties: Vec<i32>,
+    lambda: i32,
+}
+
+impl<'a> PenalizedCosts<'a> {
+    fn new(instance: &'a TsplibInstance) -> Self {
+        Self {
+            instance,
+            penalties: vec![0; instance.dimension * instance.dimension],
+            lambda: 0,
+        }
+    }
+
+    fn penalty(&self, from: usize, to: usize) -> i32 {
+        self.penalties[from * self.instance.dimension + to]
+    }
+
+    fn penalize(&mut self, from: usize, to: usize) {
+        let n = self.instance.dimension;
+        self.penalties[from * n + to] += 1;
+        self.penalties[to * n + from] += 1;
+    }
+
+    /// Penalizes the edges of `solution` with the maximal utility.
+    fn penalize_max_utility_edges(&mut self, solution: &Solution) {
+        let edges: Vec<(usize, usize)> = [&solution.cycle1, &solution.cycle2]
+            .into_iter()
+            .filter(|cycle| cycle.len() >= 2)
+            .flat_map(|cycle| (0..cycle.len()).map(|i| (cycle[i], cycle[(i + 1) % cycle.len()])))
+            .collect();
+        let utility = |&(u, v): &(usize, usize)| {
+            self.instance.distance(u, v) as f64 / (1 + self.penalty(u, v)) as f64
+        };
+        let max_utility = edges.iter().map(utility).fold(f64::MIN, f64::max);
+        let selected: Vec<(usize, usize)> = edges
+            .iter()
+            .filter(|edge| utility(edge) >= max_utility)
+            .copied()
+            .collect();
+        for (u, v) in selected {
+            self.penalize(u, v);
+        }
+    }
+}
+
+impl EdgeCost for PenalizedCosts<'_> {
+    fn edge_cost(&self, from: usize, to: usize) -> i32 {
+        self.instance.distance(from, to) + self.lambda * self.penalty(from, to)
+    }
+}
+
+pub struct GuidedLocalSearch {
+    local_search: LocalSearch,
+    /// `lambda = alpha * cost / n` of the first local optimum; 0.1-0.3 works well for TSP.
+    alpha: f64,
+    name_str: String,
+}
+
+impl GuidedLocalSearch {
+    pub fn new(local_search: LocalSearch, alpha: f64) -> Self {
+        let name_str = format!(
+            "Guided Local Search (alpha={}, ls_var={:?}, ls_neigh={:?})",
+            alpha, local_search.variant, local_search.neighborhood
+        );
+        Self {
+            local_search,
+            alpha,
+            name_str,
+        }
+    }
+}
+
+impl BudgetedAlgorithm for GuidedLocalSearch {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let mut costs = PenalizedCosts::new(instance);
+
+        let initial_solution =
+            generate_initial_solution(instance, self.local_search.initial_solution_type);
+        let mut current_solution = self
+            .local_search
+            .solve_from_solution_until(instance, initial_solution, &ls_termination, &mut |event| {
+                progress_callback(event.nested("Initial LS"))
+            })
+            .solution;
+        let mut best_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        progress.record_evaluation(best_cost);
+        costs.lambda =
+            ((self.alpha * best_cost as f64 / instance.dimension as f64).round() as i32).max(1);
+        progress_callback(ProgressEvent::Message(format!(
+            "[{}] Initial LS cost: {}, lambda: {}",
+            self.name_str, best_cost, costs.lambda
+        )));
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            costs.penalize_max_utility_edges(&current_solution);
+            current_solution = self
+                .local_search
+                .solve_from_solution_with_costs(
+                    instance,
+                    &costs,
+                    current_solution,
+                    &ls_termination,
+                    &mut |event| {
+                        progress_callback(event.nested(format!("Iter {} penalized LS", iterations)))
+                    },
+                )
+                .solution;
+            let current_cost = current_solution.calculate_cost(instance);
+            progress.record_evaluation(current_cost);
+
+            if current_cost < best_cost {
+                best_cost = current_cost;
+                best_solution = current_solution.clone();
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for GuidedLocalSearch {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
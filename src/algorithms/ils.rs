@@ -1,33 +1,76 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
 use crate::algorithms::local_search::base::LocalSearch;
 use crate::algorithms::perturbation::Perturbation;
+use crate::termination::{SearchProgress, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 use crate::utils::generate_random_solution;
-use rand::{Rng, thread_rng};
-use std::marker::PhantomData;
+use rand::{Rng, rng};
+use std::ops::RangeInclusive;
 use std::time::{Duration, Instant};
 
-// Make Ils generic over the perturbation type P
-pub struct Ils<P: Perturbation + Send + Sync> {
+/// Decides whether the local optimum reached from the perturbed solution replaces the
+/// current solution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AcceptanceCriterion {
+    /// Accept only strictly better solutions.
+    Better,
+    /// Accept solutions at least as good as the current one.
+    BetterOrEqual,
+    /// Always accept.
+    RandomWalk,
+    /// Large-step Markov chain: accept worse solutions with probability
+    /// `exp(-(new - current) / temperature)`.
+    Lsmc { temperature: f64 },
+    /// Accept only better solutions; after `iterations` iterations without a new best
+    /// solution, restart from a fresh local optimum.
+    RestartAfterStagnation { iterations: usize },
+}
+
+pub struct Ils {
     base_local_search: LocalSearch,
-    perturbation: P,
+    perturbation: Box<dyn Perturbation>,
+    acceptance: AcceptanceCriterion,
+    /// Number of times the perturbation is applied per iteration. Starts at the lower bound,
+    /// grows by one whenever local search falls back into the current optimum (same edge
+    /// set) and resets once a different optimum is reached. Reported every iteration as
+    /// `ProgressEvent::PerturbationStrength`.
+    strength: RangeInclusive<usize>,
     name_str: String,
-    _marker: PhantomData<P>, // Use PhantomData if P is not used directly in struct fields
 }
 
-// Update impl block to include the generic parameter P
-impl<P: Perturbation + Send + Sync> Ils<P> {
-    pub fn new(base_local_search: LocalSearch, perturbation: P) -> Self {
+impl Ils {
+    pub fn new(
+        base_local_search: LocalSearch,
+        perturbation: Box<dyn Perturbation>,
+        acceptance: AcceptanceCriterion,
+        strength: RangeInclusive<usize>,
+    ) -> Self {
+        assert!(
+            *strength.start() > 0 && !strength.is_empty(),
+            "Perturbation strength must be a non-empty range of positive values"
+        );
+        if let AcceptanceCriterion::Lsmc { temperature } = acceptance {
+            assert!(
+                temperature.is_finite() && temperature > 0.0,
+                "LSMC temperature must be finite and positive"
+            );
+        }
         let name_str = format!(
-            "ILS (Base: {}, Perturb: {})",
+            "ILS (Base: {}, Perturb: {}, Accept: {:?}, Strength: {}..={})",
             base_local_search.name(),
-            perturbation.name()
+            perturbation.name(),
+            acceptance,
+            strength.start(),
+            strength.end()
         );
         Self {
             base_local_search,
             perturbation,
+            acceptance,
+            strength,
             name_str,
-            _marker: PhantomData,
         }
     }
 
@@ -36,100 +79,216 @@ impl<P: Perturbation + Send + Sync> Ils<P> {
         &self.name_str
     }
 
-    // solve_timed remains largely the same, but can now call perturbation.perturb directly
-    pub fn solve_timed(
+    /// Random solution improved by the base local search.
+    fn random_local_optimum(
         &self,
         instance: &TsplibInstance,
-        time_limit: Duration,
+        ls_termination: &Termination,
         progress_callback: ProgressCallback,
-    ) -> (Solution, usize) {
+    ) -> Solution {
+        self.base_local_search
+            .solve_from_solution_until(
+                instance,
+                generate_random_solution(instance),
+                ls_termination,
+                progress_callback,
+            )
+            .solution
+    }
+}
+
+/// Order-independent hash of the undirected edges of both cycles.
+///
+/// Equal solutions get equal fingerprints regardless of rotation, direction or which cycle
+/// is listed first, so a revisited optimum is told apart from a different one of equal cost.
+fn edge_set_fingerprint(solution: &Solution) -> u64 {
+    let mut fingerprint = 0u64;
+    for cycle in [&solution.cycle1, &solution.cycle2] {
+        let len = cycle.len();
+        if len < 2 {
+            continue;
+        }
+        for i in 0..len {
+            let (a, b) = (cycle[i], cycle[(i + 1) % len]);
+            let key = ((a.min(b) as u64) << 32) | b.max(a) as u64;
+            fingerprint = fingerprint.wrapping_add(mix(key));
+        }
+    }
+    fingerprint
+}
+
+/// SplitMix64 finalizer; spreads edge keys so their sum rarely collides.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+impl BudgetedAlgorithm for Ils {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
         // Return iterations count as well
-        let start_time = Instant::now();
-        let mut rng = thread_rng();
-
-        // 1. Generate Initial Solution
-        progress_callback("Generating initial random solution...".to_string());
-        let initial_solution = generate_random_solution(instance);
-
-        // 2. Apply Local Search to Initial Solution
-        progress_callback("Running initial Local Search...".to_string());
-        let mut best_solution = self
-            .base_local_search
-            .solve_with_feedback(instance, &mut |s| {
-                progress_callback(format!("Initial LS: {}", s))
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let mut rng = rng();
+
+        // 1. Initial Solution: random start improved by Local Search
+        progress_callback(ProgressEvent::Message(
+            "Running initial Local Search from a random solution...".to_string(),
+        ));
+        let mut current_solution =
+            self.random_local_optimum(instance, &ls_termination, &mut |event| {
+                progress_callback(event.nested("Initial LS"))
             });
-        let mut best_cost = best_solution.calculate_cost(instance);
-        progress_callback(format!("Initial LS finished. Cost: {}", best_cost));
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut current_fingerprint = edge_set_fingerprint(&current_solution);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress_callback(ProgressEvent::Message(format!(
+            "Initial LS finished. Cost: {}",
+            best_cost
+        )));
+        progress.record_evaluation(best_cost);
+
+        let mut strength = *self.strength.start();
+        let mut iterations_since_best = 0;
 
-        let mut iterations = 0;
-        while start_time.elapsed() < time_limit {
-            iterations += 1;
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
             let loop_start_time = Instant::now();
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
 
-            // 3. Perturbation
-            let mut current_solution = best_solution.clone();
-            // Now we can call perturb directly
-            self.perturbation
-                .perturb(&mut current_solution, instance, &mut rng);
-            progress_callback(format!("[Iter {}] Perturbed solution.", iterations));
-
-            // 4. Local Search on Perturbed Solution
-            let mut ls_callback = |s: String| {
-                progress_callback(format!(
-                    "[Iter {}] LS on perturbed: {} (Time left: {:?})",
-                    iterations,
-                    s,
-                    time_limit.saturating_sub(start_time.elapsed())
-                ));
+            // 2. Perturbation of the current solution, `strength` times
+            progress_callback(ProgressEvent::OperatorChosen {
+                iteration: iterations,
+                operator: self.perturbation.name().to_string(),
+                probability: 1.0,
+            });
+            progress_callback(ProgressEvent::PerturbationStrength {
+                iteration: iterations,
+                strength,
+            });
+            let mut candidate = current_solution.clone();
+            for _ in 0..strength {
+                self.perturbation
+                    .perturb(&mut candidate, instance, &mut rng);
+            }
+
+            // 3. Local Search on Perturbed Solution
+            let mut ls_callback = |event: ProgressEvent| {
+                progress_callback(event.nested(format!("Iter {} LS on perturbed", iterations)));
             };
-            current_solution = self
+            candidate = self
                 .base_local_search
-                .solve_with_feedback(instance, &mut ls_callback);
-            let current_cost = current_solution.calculate_cost(instance);
-
-            // 5. Acceptance Criterion (Accept if better)
-            if current_cost < best_cost {
-                best_solution = current_solution;
-                best_cost = current_cost;
-                progress_callback(format!(
-                    "[Iter {}] New best solution found: {}. Loop time: {:?}",
-                    iterations,
-                    best_cost,
-                    loop_start_time.elapsed()
-                ));
+                .solve_from_solution_until(instance, candidate, &ls_termination, &mut ls_callback)
+                .solution;
+            let candidate_cost = candidate.calculate_cost(instance);
+            let candidate_fingerprint = edge_set_fingerprint(&candidate);
+            progress.record_evaluation(candidate_cost);
+
+            // 4. Reactive strength: escape harder while we keep falling back into the same optimum
+            strength = if candidate_fingerprint == current_fingerprint {
+                (strength + 1).min(*self.strength.end())
+            } else {
+                *self.strength.start()
+            };
+
+            // 5. Acceptance Criterion
+            if candidate_cost < best_cost {
+                best_solution = candidate.clone();
+                best_cost = candidate_cost;
+                iterations_since_best = 0;
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
             } else {
-                progress_callback(format!(
-                    "[Iter {}] Solution not improved ({} >= {}). Loop time: {:?}",
+                iterations_since_best += 1;
+            }
+            let accept = match self.acceptance {
+                AcceptanceCriterion::Better
+                | AcceptanceCriterion::RestartAfterStagnation { .. } => {
+                    candidate_cost < current_cost
+                }
+                AcceptanceCriterion::BetterOrEqual => candidate_cost <= current_cost,
+                AcceptanceCriterion::RandomWalk => true,
+                AcceptanceCriterion::Lsmc { temperature } => {
+                    candidate_cost <= current_cost
+                        || rng.random_bool(
+                            (-((candidate_cost - current_cost) as f64) / temperature).exp(),
+                        )
+                }
+            };
+            if accept {
+                current_solution = candidate;
+                current_cost = candidate_cost;
+                current_fingerprint = candidate_fingerprint;
+            } else {
+                progress_callback(ProgressEvent::Message(format!(
+                    "[Iter {}] Candidate rejected ({} vs current {}). Loop time: {:?}",
                     iterations,
+                    candidate_cost,
                     current_cost,
-                    best_cost,
                     loop_start_time.elapsed()
-                ));
+                )));
             }
 
-            // Check time limit again before next iteration
-            if start_time.elapsed() >= time_limit {
-                progress_callback(format!("[Iter {}] Time limit reached.", iterations));
-                break;
+            if let AcceptanceCriterion::RestartAfterStagnation { iterations: limit } =
+                self.acceptance
+                && iterations_since_best >= limit
+            {
+                progress_callback(ProgressEvent::Message(format!(
+                    "[Iter {}] No new best for {} iterations, restarting.",
+                    iterations, iterations_since_best
+                )));
+                current_solution =
+                    self.random_local_optimum(instance, &ls_termination, &mut |event| {
+                        progress_callback(event.nested(format!("Iter {} restart LS", iterations)))
+                    });
+                current_cost = current_solution.calculate_cost(instance);
+                current_fingerprint = edge_set_fingerprint(&current_solution);
+                progress.record_evaluation(current_cost);
+                strength = *self.strength.start();
+                iterations_since_best = 0;
             }
-        }
 
-        progress_callback(format!(
-            "ILS finished. Total iterations: {}, Best cost: {}, Total time: {:?}",
-            iterations,
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+
+            // Check the termination criteria again before next iteration
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
             best_cost,
-            start_time.elapsed()
-        ));
-        (best_solution, iterations)
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
     }
 }
 
-// We might need a way to run ILS with a time limit based on MSLS average time.
-// TspAlgorithm trait expects solve_with_feedback which doesn't naturally take a time limit.
-// For now, we'll add a separate solve_timed method.
-// Alternatively, we could adapt the TspAlgorithm trait or ExperimentRunner later.
-
-/*
 impl TspAlgorithm for Ils {
     fn name(&self) -> &str {
         &self.name_str
@@ -140,8 +299,13 @@ impl TspAlgorithm for Ils {
         instance: &TsplibInstance,
         progress_callback: ProgressCallback,
     ) -> Solution {
-        // This would need a default time limit or another way to determine it.
-        unimplemented!("ILS solve_with_feedback needs time limit handling.");
+        // Same default as the other metaheuristics when no budget is given
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
     }
 }
-*/
@@ -0,0 +1,150 @@
+//! Late acceptance hill climbing over random, delta-evaluated moves.
+//!
+//! A candidate is accepted if it is no worse than the current solution or than the
+//! current solution `history_length` iterations ago. The history list replaces the
+//! temperature of simulated annealing with a single, scale-free parameter.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+    reports_move_iteration,
+};
+use crate::algorithms::local_search::base::{
+    InitialSolutionType, NeighborhoodType, generate_initial_solution,
+};
+use crate::moves::random::random_evaluated_move;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::rng;
+use std::time::Duration;
+
+pub struct LateAcceptanceHillClimbing {
+    /// Intra-route moves: `VertexExchange` or `EdgeExchange`.
+    neighborhood: NeighborhoodType,
+    /// Number of past current costs remembered by the acceptance test.
+    history_length: usize,
+    initial_solution_type: InitialSolutionType,
+    name_str: String,
+}
+
+impl LateAcceptanceHillClimbing {
+    pub fn new(
+        neighborhood: NeighborhoodType,
+        history_length: usize,
+        initial_solution_type: InitialSolutionType,
+    ) -> Self {
+        assert!(
+            matches!(
+                neighborhood,
+                NeighborhoodType::VertexExchange | NeighborhoodType::EdgeExchange
+            ),
+            "Late acceptance supports only vertex and edge exchange neighborhoods"
+        );
+        assert!(history_length > 0, "History length must be positive");
+        let name_str = format!(
+            "Late Acceptance Hill Climbing ({:?}, L={}, init={:?})",
+            neighborhood, history_length, initial_solution_type
+        );
+        Self {
+            neighborhood,
+            history_length,
+            initial_solution_type,
+            name_str,
+        }
+    }
+}
+
+impl BudgetedAlgorithm for LateAcceptanceHillClimbing {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+
+        let mut current_solution = generate_initial_solution(instance, self.initial_solution_type);
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress.record_evaluation(current_cost);
+        let mut history = vec![current_cost; self.history_length];
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationStarted {
+                    iteration: iterations,
+                    best_cost,
+                });
+            }
+
+            let slot = iterations % self.history_length;
+            if let Some(candidate) =
+                random_evaluated_move(&current_solution, instance, self.neighborhood, &mut rng)
+            {
+                let candidate_cost = current_cost + candidate.delta;
+                // Rejected moves are worsening, so counting them cannot move the best cost
+                progress.record_evaluation(candidate_cost);
+                if candidate.delta <= 0 || candidate_cost <= history[slot] {
+                    candidate.move_type.apply(&mut current_solution);
+                    current_cost = candidate_cost;
+                    if current_cost < best_cost {
+                        best_cost = current_cost;
+                        best_solution = current_solution.clone();
+                        progress_callback(ProgressEvent::NewBest {
+                            iteration: iterations,
+                            cost: best_cost,
+                            elapsed: progress.elapsed(),
+                        });
+                    }
+                }
+            }
+            // Only lower the history entry, so the acceptance bound never loosens again
+            history[slot] = history[slot].min(current_cost);
+
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationFinished {
+                    iteration: iterations,
+                    current_cost,
+                    best_cost,
+                });
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for LateAcceptanceHillClimbing {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
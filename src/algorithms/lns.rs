@@ -1,27 +1,26 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
 use crate::algorithms::local_search::base::LocalSearch;
 use crate::algorithms::perturbation::Perturbation;
+use crate::termination::{SearchProgress, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 use crate::utils::generate_random_solution;
-use rand::{Rng, thread_rng};
-use std::marker::PhantomData;
+use rand::rng;
 use std::time::{Duration, Instant};
 
-// Make Lns generic over the perturbation type P
-pub struct Lns<P: Perturbation + Send + Sync> {
+pub struct Lns {
     base_local_search: LocalSearch,
-    perturbation: P, // Should be a Destroy/Repair type
+    perturbation: Box<dyn Perturbation>, // Should be a Destroy/Repair type
     apply_ls_after_repair: bool,
     apply_ls_to_initial: bool,
     name_str: String,
-    _marker: PhantomData<P>,
 }
 
-// Update impl block to include the generic parameter P
-impl<P: Perturbation + Send + Sync> Lns<P> {
+impl Lns {
     pub fn new(
         base_local_search: LocalSearch,
-        perturbation: P,
+        perturbation: Box<dyn Perturbation>,
         apply_ls_after_repair: bool,
         apply_ls_to_initial: bool, // LNSa variant check
     ) -> Self {
@@ -48,7 +47,6 @@ impl<P: Perturbation + Send + Sync> Lns<P> {
             apply_ls_after_repair,
             apply_ls_to_initial,
             name_str,
-            _marker: PhantomData,
         }
     }
 
@@ -56,107 +54,130 @@ impl<P: Perturbation + Send + Sync> Lns<P> {
     pub fn name(&self) -> &str {
         &self.name_str
     }
+}
 
-    // solve_timed remains largely the same, but can now call perturbation.perturb directly
-    pub fn solve_timed(
+impl BudgetedAlgorithm for Lns {
+    fn solve_budgeted(
         &self,
         instance: &TsplibInstance,
-        time_limit: Duration,
+        termination: &Termination,
         progress_callback: ProgressCallback,
-    ) -> (Solution, usize) {
+    ) -> SolveOutcome {
         // Return iterations count as well
-        let start_time = Instant::now();
-        let mut rng = thread_rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let mut rng = rng();
 
         // 1. Generate Initial Solution
-        progress_callback("Generating initial random solution...".to_string());
+        progress_callback(ProgressEvent::Message(
+            "Generating initial random solution...".to_string(),
+        ));
         let mut best_solution = generate_random_solution(instance);
 
         // 2. Apply Local Search to Initial Solution (Optional)
         if self.apply_ls_to_initial {
-            progress_callback("Running initial Local Search...".to_string());
+            progress_callback(ProgressEvent::Message(
+                "Running initial Local Search...".to_string(),
+            ));
             best_solution = self
                 .base_local_search
-                .solve_with_feedback(instance, &mut |s| {
-                    progress_callback(format!("Initial LS: {}", s))
-                });
-            progress_callback(format!(
+                .solve_from_solution_until(
+                    instance,
+                    best_solution,
+                    &ls_termination,
+                    &mut |event| progress_callback(event.nested("Initial LS")),
+                )
+                .solution;
+            progress_callback(ProgressEvent::Message(format!(
                 "Initial LS finished. Cost: {}",
                 best_solution.calculate_cost(instance)
-            ));
+            )));
         }
         let mut best_cost = best_solution.calculate_cost(instance);
+        progress.record_evaluation(best_cost);
 
-        let mut iterations = 0;
-        while start_time.elapsed() < time_limit {
-            iterations += 1;
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
             let loop_start_time = Instant::now();
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
 
             // 3. Perturbation (Destroy + Repair)
             let mut current_solution = best_solution.clone();
-            // Now we can call perturb directly
             self.perturbation
                 .perturb(&mut current_solution, instance, &mut rng);
-            progress_callback(format!(
+            progress_callback(ProgressEvent::Message(format!(
                 "[Iter {}] Perturbed (Destroy/Repair) solution.",
                 iterations
-            ));
+            )));
 
             // 4. Local Search on Repaired Solution (Optional)
             if self.apply_ls_after_repair {
-                let mut ls_callback = |s: String| {
-                    progress_callback(format!(
-                        "[Iter {}] LS on repaired: {} (Time left: {:?})",
-                        iterations,
-                        s,
-                        time_limit.saturating_sub(start_time.elapsed())
-                    ));
+                let mut ls_callback = |event: ProgressEvent| {
+                    progress_callback(event.nested(format!("Iter {} LS on repaired", iterations)));
                 };
                 current_solution = self
                     .base_local_search
-                    .solve_with_feedback(instance, &mut ls_callback);
+                    .solve_from_solution_until(
+                        instance,
+                        current_solution,
+                        &ls_termination,
+                        &mut ls_callback,
+                    )
+                    .solution;
             }
             let current_cost = current_solution.calculate_cost(instance);
+            progress.record_evaluation(current_cost);
 
             // 5. Acceptance Criterion (Accept if better)
             if current_cost < best_cost {
                 best_solution = current_solution;
                 best_cost = current_cost;
-                progress_callback(format!(
-                    "[Iter {}] New best solution found: {}. Loop time: {:?}",
-                    iterations,
-                    best_cost,
-                    loop_start_time.elapsed()
-                ));
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
             } else {
-                progress_callback(format!(
+                progress_callback(ProgressEvent::Message(format!(
                     "[Iter {}] Solution not improved ({} >= {}). Loop time: {:?}",
                     iterations,
                     current_cost,
                     best_cost,
                     loop_start_time.elapsed()
-                ));
+                )));
             }
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
 
-            // Check time limit again before next iteration
-            if start_time.elapsed() >= time_limit {
-                progress_callback(format!("[Iter {}] Time limit reached.", iterations));
-                break;
+            // Check the termination criteria again before next iteration
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
             }
-        }
+        };
 
-        progress_callback(format!(
-            "LNS finished. Total iterations: {}, Best cost: {}, Total time: {:?}",
-            iterations,
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
             best_cost,
-            start_time.elapsed()
-        ));
-        (best_solution, iterations)
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
     }
 }
 
-// Similar time limit considerations as ILS apply here.
-/*
 impl TspAlgorithm for Lns {
     fn name(&self) -> &str {
         &self.name_str
@@ -167,7 +188,13 @@ impl TspAlgorithm for Lns {
         instance: &TsplibInstance,
         progress_callback: ProgressCallback,
     ) -> Solution {
-        unimplemented!("LNS solve_with_feedback needs time limit handling.");
+        // Same default as the other metaheuristics when no budget is given
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
     }
 }
-*/
@@ -1,5 +1,8 @@
-use crate::algorithm::ProgressCallback;
-use crate::algorithm::TspAlgorithm;
+use crate::algorithm::{ProgressCallback, ProgressEvent};
+use crate::algorithm::{BudgetedAlgorithm, SolveOutcome, TspAlgorithm};
+use crate::algorithms::constructive::route_first_cluster_second::{
+    GiantTour, RouteFirstClusterSecond,
+};
 use crate::algorithms::constructive::weighted_regret_cycle::WeightedRegretCycle;
 use crate::moves::inter_route::evaluate_inter_route_exchange;
 use crate::moves::intra_route::{
@@ -8,7 +11,8 @@ use crate::moves::intra_route::{
     evaluate_intra_route_or_opt,
 };
 use crate::moves::types::{CycleId, EvaluatedMove, Move};
-use crate::tsplib::{Solution, TsplibInstance};
+use crate::termination::{SearchProgress, StopReason, Termination};
+use crate::tsplib::{EdgeCost, Solution, TsplibInstance};
 use crate::utils::generate_random_solution;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -39,6 +43,30 @@ pub enum InitialSolutionType {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HeuristicAlgorithm {
     WeightedRegret,
+    /// Giant tour of the given kind, optimally split into two cycles.
+    RouteFirstClusterSecond(GiantTour),
+}
+
+/// Builds a starting solution of the given type.
+pub(crate) fn generate_initial_solution(
+    instance: &TsplibInstance,
+    initial_solution_type: InitialSolutionType,
+) -> Solution {
+    match initial_solution_type {
+        InitialSolutionType::Random => generate_random_solution(instance),
+        InitialSolutionType::Heuristic(heuristic) => match heuristic {
+            HeuristicAlgorithm::WeightedRegret => {
+                let constructive_algo = WeightedRegretCycle::default();
+                let mut dummy_callback = |_: ProgressEvent| {};
+                constructive_algo.solve_with_feedback(instance, &mut dummy_callback)
+            }
+            HeuristicAlgorithm::RouteFirstClusterSecond(giant_tour) => {
+                let constructive_algo = RouteFirstClusterSecond::new(giant_tour);
+                let mut dummy_callback = |_: ProgressEvent| {};
+                constructive_algo.solve_with_feedback(instance, &mut dummy_callback)
+            }
+        },
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -86,16 +114,7 @@ impl LocalSearch {
     }
 
     fn generate_initial_solution(&self, instance: &TsplibInstance) -> Solution {
-        match self.initial_solution_type {
-            InitialSolutionType::Random => generate_random_solution(instance),
-            InitialSolutionType::Heuristic(heuristic) => match heuristic {
-                HeuristicAlgorithm::WeightedRegret => {
-                    let constructive_algo = WeightedRegretCycle::default();
-                    let mut dummy_callback = |_: String| {};
-                    constructive_algo.solve_with_feedback(instance, &mut dummy_callback)
-                }
-            },
-        }
+        generate_initial_solution(instance, self.initial_solution_type)
     }
     
     /// Perform local search starting from the given solution
@@ -105,29 +124,67 @@ impl LocalSearch {
         initial_solution: Solution,
         progress_callback: ProgressCallback,
     ) -> Solution {
-        self.apply_local_search(instance, initial_solution, progress_callback)
+        self.apply_local_search(instance, instance, initial_solution, None, progress_callback)
+            .solution
+    }
+
+    /// Like `solve_from_solution`, but stops early (keeping the moves applied so far)
+    /// once `termination` fires.
+    pub fn solve_from_solution_until(
+        &self,
+        instance: &TsplibInstance,
+        initial_solution: Solution,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        self.apply_local_search(instance, instance, initial_solution, Some(termination), progress_callback)
+    }
+
+    /// Like `solve_from_solution_until`, but minimizes `costs` instead of the instance
+    /// distances. `instance` still provides the nearest-neighbour candidate lists.
+    pub fn solve_from_solution_with_costs<C: EdgeCost + ?Sized>(
+        &self,
+        instance: &TsplibInstance,
+        costs: &C,
+        initial_solution: Solution,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        self.apply_local_search(instance, costs, initial_solution, Some(termination), progress_callback)
     }
     
-    fn apply_local_search(
+    /// Improves `current_solution` until a local optimum is reached or `termination` fires.
+    /// Each iteration is one applied move.
+    fn apply_local_search<C: EdgeCost + ?Sized>(
         &self,
         instance: &TsplibInstance,
+        costs: &C,
         mut current_solution: Solution,
+        termination: Option<&Termination>,
         mut progress_callback: ProgressCallback,
-    ) -> Solution {
-        let mut current_cost = current_solution.calculate_cost(instance);
+    ) -> SolveOutcome {
+        let mut current_cost = current_solution.calculate_cost_with(costs);
         let mut rng = thread_rng();
-        let mut iteration = 0;
+        let mut progress = SearchProgress::new();
+        progress.record_evaluation(current_cost);
 
         let mut move_list: Vec<EvaluatedMove> = Vec::new();
         if self.variant == SearchVariant::MoveListSteepest {
-            move_list = self.generate_all_improving_moves(instance, &current_solution);
+            move_list = self.generate_all_improving_moves(costs, &current_solution);
             move_list.sort_unstable_by_key(|m| m.delta);
         }
 
-        loop {
-            iteration += 1;
+        let stop_reason = loop {
+            if let Some(reason) = termination.and_then(|t| t.check(&progress)) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iteration = progress.iterations;
             let cost_before_iter = current_cost;
-            progress_callback(format!("[Iter: {}] Cost: {}", iteration, current_cost));
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration,
+                best_cost: current_cost,
+            });
 
             let mut best_evaluated_move: Option<EvaluatedMove> = None;
             let mut found_improving_move = false;
@@ -138,11 +195,11 @@ impl LocalSearch {
             match self.variant {
                 SearchVariant::Steepest | SearchVariant::Greedy => {
                     current_improving_moves =
-                        self.generate_all_improving_moves(instance, &current_solution);
+                        self.generate_all_improving_moves(costs, &current_solution);
                 }
                 SearchVariant::CandidateSteepest(k) => {
                     current_improving_moves =
-                        self.generate_candidate_moves(instance, &current_solution, k);
+                        self.generate_candidate_moves(instance, costs, &current_solution, k);
                 }
                 SearchVariant::MoveListSteepest => {}
             }
@@ -188,7 +245,7 @@ impl LocalSearch {
                 applied_move.move_type.apply(&mut current_solution);
                 current_cost += applied_move.delta;
 
-                let real_cost_after_apply = current_solution.calculate_cost(instance);
+                let real_cost_after_apply = current_solution.calculate_cost_with(costs);
                 if real_cost_after_apply != current_cost {
                     eprintln!(
                         "[WARN] Cost mismatch after apply! Iter: {}, Move: {:?}, Delta: {}, Cost before: {}, Incremental cost: {}, Real cost: {}",
@@ -213,7 +270,7 @@ impl LocalSearch {
                             .retain(|m| !self.move_involves_nodes(&m.move_type, &affected_nodes));
 
                         let new_potential_moves = self.generate_moves_around_nodes(
-                            instance,
+                            costs,
                             &current_solution,
                             &affected_nodes,
                         );
@@ -236,22 +293,38 @@ impl LocalSearch {
                     }
                 }
                 if current_cost >= cost_before_iter {
-                    progress_callback(format!(
-                        "[Finished] No significant cost improvement. Final Cost: {}",
-                        current_cost
-                    ));
-                    break;
+                    // No significant cost improvement
+                    break StopReason::Converged;
                 }
+                progress.record_evaluation(current_cost);
+                progress_callback(ProgressEvent::IterationFinished {
+                    iteration,
+                    current_cost,
+                    best_cost: current_cost,
+                });
             } else {
-                progress_callback(format!(
-                    "[Finished] Local optimum found or no improving moves. Final Cost: {}",
-                    current_cost
-                ));
-                break;
+                // Local optimum found or no improving moves
+                break StopReason::Converged;
             }
-        }
+        };
 
-        current_solution
+        if stop_reason == StopReason::Converged {
+            progress_callback(ProgressEvent::LocalOptimumReached {
+                cost: current_cost,
+                iterations: progress.iterations,
+            });
+        }
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost: current_cost,
+            stop_reason,
+        });
+
+        SolveOutcome {
+            solution: current_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
     }
 }
 
@@ -266,7 +339,20 @@ impl TspAlgorithm for LocalSearch {
         progress_callback: ProgressCallback,
     ) -> Solution {
         let initial_solution = self.generate_initial_solution(instance);
-        self.apply_local_search(instance, initial_solution, progress_callback)
+        self.apply_local_search(instance, instance, initial_solution, None, progress_callback)
+            .solution
+    }
+}
+
+impl BudgetedAlgorithm for LocalSearch {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let initial_solution = self.generate_initial_solution(instance);
+        self.apply_local_search(instance, instance, initial_solution, Some(termination), progress_callback)
     }
 }
 
@@ -287,9 +373,9 @@ impl LocalSearch {
         }
     }
 
-    fn generate_all_improving_moves(
+    fn generate_all_improving_moves<C: EdgeCost + ?Sized>(
         &self,
-        instance: &TsplibInstance,
+        costs: &C,
         solution: &Solution,
     ) -> Vec<EvaluatedMove> {
         let mut moves = Vec::new();
@@ -299,7 +385,7 @@ impl LocalSearch {
         if solution.cycle1.len() > 0 && solution.cycle2.len() > 0 { // Ensure cycles are not empty
             for pos1 in 0..solution.cycle1.len() {
                 for pos2 in 0..solution.cycle2.len() {
-                    if let Some(m) = evaluate_inter_route_exchange(solution, instance, pos1, pos2) {
+                    if let Some(m) = evaluate_inter_route_exchange(solution, costs, pos1, pos2) {
                         if m.delta < 0 {
                             moves.push(m);
                         }
@@ -318,7 +404,7 @@ impl LocalSearch {
                         for pos1 in 0..n {
                             for pos2 in pos1 + 1..n {
                                 if let Some(m) = evaluate_intra_route_vertex_exchange(
-                                    solution, instance, cycle_id, pos1, pos2,
+                                    solution, costs, cycle_id, pos1, pos2,
                                 ) {
                                     if m.delta < 0 {
                                         moves.push(m);
@@ -343,7 +429,7 @@ impl LocalSearch {
                                     continue;
                                 }
                                 if let Some(m) = evaluate_intra_route_edge_exchange(
-                                    solution, instance, cycle_id, pos1, pos2,
+                                    solution, costs, cycle_id, pos1, pos2,
                                 ) {
                                     if m.delta < 0 {
                                         moves.push(m);
@@ -364,13 +450,13 @@ impl LocalSearch {
                                         continue;
                                     }
                                     if let Some(mut m) = evaluate_intra_route_3opt(
-                                        solution, instance, cycle_id, pos1, pos2, pos3,
+                                        solution, costs, cycle_id, pos1, pos2, pos3,
                                     ) {
                                         if self.verify_delta_computationally {
-                                            let cost_before_move = solution.calculate_cost(instance);
+                                            let cost_before_move = solution.calculate_cost_with(costs);
                                             let mut temp_solution = solution.clone();
                                             m.move_type.apply(&mut temp_solution);
-                                            let cost_after_move = temp_solution.calculate_cost(instance);
+                                            let cost_after_move = temp_solution.calculate_cost_with(costs);
                                             let true_delta = cost_after_move - cost_before_move;
                                             if true_delta < 0 {
                                                 m.delta = true_delta;
@@ -392,14 +478,14 @@ impl LocalSearch {
                             for from_pos in 0..n {
                                 for to_pos in 0..n { // to_pos is index of node to insert BEFORE
                                     if let Some(mut m) = evaluate_intra_route_or_opt(
-                                        solution, instance, cycle_id, from_pos, chain_length, to_pos,
+                                        solution, costs, cycle_id, from_pos, chain_length, to_pos,
                                     ) {
                                         // evaluate_intra_route_or_opt already returns delta < 0 moves
                                         if self.verify_delta_computationally {
-                                            let cost_before_move = solution.calculate_cost(instance);
+                                            let cost_before_move = solution.calculate_cost_with(costs);
                                             let mut temp_solution = solution.clone();
                                             m.move_type.apply(&mut temp_solution);
-                                            let cost_after_move = temp_solution.calculate_cost(instance);
+                                            let cost_after_move = temp_solution.calculate_cost_with(costs);
                                             let true_delta = cost_after_move - cost_before_move;
 
                                             if true_delta < 0 {
@@ -420,9 +506,10 @@ impl LocalSearch {
         moves
     }
 
-    fn generate_candidate_moves(
+    fn generate_candidate_moves<C: EdgeCost + ?Sized>(
         &self,
         instance: &TsplibInstance,
+        costs: &C,
         solution: &Solution,
         k: usize,
     ) -> Vec<EvaluatedMove> {
@@ -453,7 +540,7 @@ impl LocalSearch {
                     };
                     if let Some(m) = evaluate_inter_route_exchange(
                         solution,
-                        instance,
+                        costs,
                         actual_pos_a,
                         actual_pos_b,
                     ) {
@@ -465,7 +552,7 @@ impl LocalSearch {
                     match self.neighborhood {
                         NeighborhoodType::EdgeExchange => {
                             if let Some(m) = evaluate_candidate_intra_route_edge_exchange(
-                                solution, instance, cycle_a, pos_a, pos_b,
+                                solution, costs, cycle_a, pos_a, pos_b,
                             ) {
                                 if m.delta < 0 {
                                     moves.push(m);
@@ -474,7 +561,7 @@ impl LocalSearch {
                         }
                         NeighborhoodType::VertexExchange => {
                             if let Some(m) = evaluate_intra_route_vertex_exchange(
-                                solution, instance, cycle_a, pos_a, pos_b,
+                                solution, costs, cycle_a, pos_a, pos_b,
                             ) {
                                 if m.delta < 0 {
                                     moves.push(m);
@@ -606,9 +693,9 @@ impl LocalSearch {
         }
     }
 
-    fn generate_moves_around_nodes(
+    fn generate_moves_around_nodes<C: EdgeCost + ?Sized>(
         &self,
-        instance: &TsplibInstance,
+        costs: &C,
         solution: &Solution,
         affected_nodes: &HashSet<usize>,
     ) -> Vec<EvaluatedMove> {
@@ -642,7 +729,7 @@ impl LocalSearch {
                             (pos_b, pos_a)
                         };
                         if let Some(m) =
-                            evaluate_inter_route_exchange(solution, instance, eval_pos1, eval_pos2)
+                            evaluate_inter_route_exchange(solution, costs, eval_pos1, eval_pos2)
                         {
                             if m.delta < 0 {
                                 new_moves.push(m);
@@ -668,7 +755,7 @@ impl LocalSearch {
                             };
                             if considered_vertex_pairs.insert(pair) {
                                 if let Some(m) = evaluate_intra_route_vertex_exchange(
-                                    solution, instance, cycle_id_a, pos_a, pos_b,
+                                    solution, costs, cycle_id_a, pos_a, pos_b,
                                 ) {
                                     if m.delta < 0 {
                                         new_moves.push(m);
@@ -680,7 +767,7 @@ impl LocalSearch {
                             let diff = (pos_a as isize - pos_b as isize).abs();
                             if n >= 3 && diff != 1 && diff != (n - 1) as isize {
                                 if let Some(m) = evaluate_intra_route_edge_exchange(
-                                    solution, instance, cycle_id_a, pos_a, pos_b,
+                                    solution, costs, cycle_id_a, pos_a, pos_b,
                                 ) {
                                     if m.delta < 0 {
                                         new_moves.push(m);
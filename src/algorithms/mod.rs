@@ -1,12 +1,24 @@
+pub mod alns;
+pub mod ant_colony;
 pub mod constructive;
+pub mod grasp;
+pub mod guided_local_search;
 pub mod ils;
+pub mod late_acceptance;
 pub mod lns;
 pub mod local_search;
 pub mod msls;
+pub mod path_relinking;
 pub mod perturbation;
 pub mod random_walk;
+pub mod recombination;
+pub mod simulated_annealing;
+pub mod tabu_search;
+pub mod threshold_accepting;
 pub mod hae;
 pub mod avns;
 pub mod enhanced_hae;
 pub mod enhanced_hae_3opt;
 pub mod enhanced_hae_or_opt;
+pub mod evolutionary;
+pub mod exact;
@@ -1,5 +1,8 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
 use crate::algorithms::local_search::base::LocalSearch;
+use crate::termination::{SearchProgress, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 // Removed: use crate::utils::generate_random_solution; // Not used directly here
 use std::time::Instant;
@@ -27,25 +30,28 @@ impl Msls {
     }
 }
 
-impl TspAlgorithm for Msls {
-    fn name(&self) -> &str {
-        &self.name_str
-    }
-
-    fn solve_with_feedback(
+impl BudgetedAlgorithm for Msls {
+    /// Each iteration is one local search from a fresh start; at least one start is always run.
+    fn solve_budgeted(
         &self,
         instance: &TsplibInstance,
+        termination: &Termination,
         mut progress_callback: ProgressCallback,
-    ) -> Solution {
+    ) -> SolveOutcome {
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
         let mut best_solution: Option<Solution> = None;
         let mut best_cost = i32::MAX;
 
-        progress_callback(format!(
-            "[{}] Starting MSLS with {} random starts.",
-            self.name_str, self.num_starts
-        ));
+        progress_callback(ProgressEvent::Message(format!("[{}] Starting MSLS.", self.name_str)));
 
-        for i in 0..self.num_starts {
+        let stop_reason = loop {
+            progress.iterations += 1;
+            let start = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: start,
+                best_cost,
+            });
             let ls_for_this_start = LocalSearch::new(
                 self.base_local_search.variant,
                 self.base_local_search.neighborhood,
@@ -53,49 +59,77 @@ impl TspAlgorithm for Msls {
                 self.base_local_search.verify_delta_computationally, // Pass the flag
             );
 
-            progress_callback(format!(
-                "[{}] Start {}/{}: Running Local Search ({:?}, {:?}, Init: {:?}, Verify={})...",
+            progress_callback(ProgressEvent::Message(format!(
+                "[{}] Start {}: Running Local Search ({:?}, {:?}, Init: {:?}, Verify={})...",
                 self.name_str,
-                i + 1,
-                self.num_starts,
+                start,
                 ls_for_this_start.variant,
                 ls_for_this_start.neighborhood,
                 ls_for_this_start.initial_solution_type,
                 ls_for_this_start.verify_delta_computationally
-            ));
+            )));
 
-            let current_solution = ls_for_this_start.solve_with_feedback(instance, &mut |s_ls| {
-                progress_callback(format!(
-                    "  [{}] Start {}, LS [{}]: {}",
-                    self.name_str,
-                    i + 1,
-                    ls_for_this_start.name_str,
-                    s_ls
-                ));
-            });
+            let current_solution = ls_for_this_start.solve_budgeted(instance, &ls_termination, &mut |event| {
+                progress_callback(event.nested(format!(
+                    "Start {}, {}",
+                    start, ls_for_this_start.name_str
+                )));
+            }).solution;
             let current_cost = current_solution.calculate_cost(instance);
+            progress.record_evaluation(current_cost);
 
-            progress_callback(format!(
-                "[{}] Start {}/{}: Completed. Cost: {}",
-                self.name_str, 
-                i + 1, 
-                self.num_starts, 
-                current_cost
-            ));
+            progress_callback(ProgressEvent::Message(format!(
+                "[{}] Start {}: Completed. Cost: {}",
+                self.name_str, start, current_cost
+            )));
 
             if current_cost < best_cost {
                 best_cost = current_cost;
                 best_solution = Some(current_solution);
-                progress_callback(format!(
-                    "[{}] New best MSLS cost: {}",
-                    self.name_str, best_cost
-                ));
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: start,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: start,
+                current_cost,
+                best_cost,
+            });
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
             }
+        };
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution.expect("MSLS should find at least one solution"),
+            iterations: progress.iterations,
+            stop_reason,
         }
-        progress_callback(format!(
-            "[{}] MSLS Finished. Final best cost: {}",
-            self.name_str, best_cost
-        ));
-        best_solution.expect("MSLS should find at least one solution")
+    }
+}
+
+impl TspAlgorithm for Msls {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        self.solve_budgeted(
+            instance,
+            &Termination::MaxIterations(self.num_starts),
+            progress_callback,
+        )
+        .solution
     }
 }
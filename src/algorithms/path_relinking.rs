@@ -0,0 +1,444 @@
+//! Path relinking between two solutions.
+//!
+//! The walk starts at the initiating solution and greedily applies the cheapest move
+//! that brings it closer to the guiding solution: an inter-route exchange of two nodes
+//! assigned to the wrong cycles, or the insertion of a node next to one of its guide
+//! neighbours when that increases the number of common edges. Points close to either
+//! end mostly fall back into that parent under local search, so only points evenly spread
+//! over the middle of the path are improved.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::evolutionary::replacement::{
+    Replacement, ReplacementState, SimilarityMeasure, SimilarityReplacement,
+};
+use crate::algorithms::local_search::base::{InitialSolutionType, LocalSearch};
+use crate::algorithms::recombination::Recombination;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore, rng};
+use std::time::Duration;
+
+/// Share of the path at each end skipped when picking points for local search.
+const PATH_END_MARGIN: f64 = 0.2;
+
+/// One step of the walk towards the guiding solution.
+#[derive(Debug, Clone, Copy)]
+enum RelinkMove {
+    /// Swap the node at `pos1` in cycle 1 with the node at `pos2` in cycle 2.
+    Exchange { pos1: usize, pos2: usize },
+    /// Move the node at `from` in cycle `cycle` to sit right after the node at `after`.
+    Insert {
+        cycle: usize,
+        from: usize,
+        after: usize,
+    },
+}
+
+pub struct PathRelinking {
+    local_search: LocalSearch,
+    /// Number of elite solutions kept by the stand-alone algorithm.
+    elite_size: usize,
+    /// Number of intermediate solutions improved with local search.
+    num_local_searches: usize,
+    name_str: String,
+}
+
+impl PathRelinking {
+    pub fn new(local_search: LocalSearch, elite_size: usize, num_local_searches: usize) -> Self {
+        let name_str = format!(
+            "Path Relinking (elite={}, ls_points={}, ls_var={:?}, ls_neigh={:?})",
+            elite_size, num_local_searches, local_search.variant, local_search.neighborhood
+        );
+        Self {
+            local_search,
+            elite_size,
+            num_local_searches,
+            name_str,
+        }
+    }
+
+    /// Walks from `initiating` towards `guiding` and returns up to `num_local_searches`
+    /// intermediate solutions: the path is split into that many evenly spaced windows and
+    /// the cheapest point of each window is returned, in path order. The first and last
+    /// `PATH_END_MARGIN` of the path (at least one step each) are skipped, so neither
+    /// parent nor its immediate neighbours are returned; short paths yield their middle point.
+    pub fn intermediate_solutions(
+        &self,
+        initiating: &Solution,
+        guiding: &Solution,
+        instance: &TsplibInstance,
+    ) -> Vec<(Solution, i32)> {
+        let n = instance.size();
+        let mut guide_neighbors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for cycle in [&guiding.cycle1, &guiding.cycle2] {
+            let len = cycle.len();
+            if len < 2 {
+                continue;
+            }
+            for i in 0..len {
+                let (u, v) = (cycle[i], cycle[(i + 1) % len]);
+                if !guide_neighbors[u].contains(&v) {
+                    guide_neighbors[u].push(v);
+                    guide_neighbors[v].push(u);
+                }
+            }
+        }
+
+        // Guide cycle each node should end up in, with cycle labels matched to maximise overlap
+        let mut guide_cycle = vec![0; n];
+        for &v in &guiding.cycle2 {
+            guide_cycle[v] = 1;
+        }
+        let overlap = initiating
+            .cycle1
+            .iter()
+            .filter(|&&v| guide_cycle[v] == 0)
+            .count()
+            + initiating
+                .cycle2
+                .iter()
+                .filter(|&&v| guide_cycle[v] == 1)
+                .count();
+        if 2 * overlap < n {
+            guide_cycle.iter_mut().for_each(|c| *c = 1 - *c);
+        }
+
+        let mut current = initiating.clone();
+        let mut current_cost = current.calculate_cost(instance);
+        let mut path: Vec<(Solution, i32)> = Vec::new();
+        while let Some((relink_move, delta)) =
+            best_relink_move(&current, &guide_cycle, &guide_neighbors, instance)
+        {
+            apply_relink_move(&mut current, relink_move);
+            current_cost += delta;
+            path.push((current.clone(), current_cost));
+        }
+
+        let len = path.len();
+        let margin = ((len as f64 * PATH_END_MARGIN).ceil() as usize).max(1);
+        let (start, window) = if len > 2 * margin {
+            (margin, len - 2 * margin)
+        } else if len >= 2 {
+            ((len - 1) / 2, 1)
+        } else {
+            return Vec::new();
+        };
+        let count = self.num_local_searches.clamp(1, window);
+        let picked: Vec<usize> = (0..count)
+            .filter_map(|i| {
+                let window_range = start + i * window / count..start + (i + 1) * window / count;
+                window_range.min_by_key(|&j| path[j].1)
+            })
+            .collect();
+        path.into_iter()
+            .enumerate()
+            .filter(|(i, _)| picked.contains(i))
+            .map(|(_, point)| point)
+            .collect()
+    }
+
+    /// Best local optimum reached from the path between `initiating` and `guiding`,
+    /// or `initiating` itself if the path is too short to have intermediate points.
+    fn relink(
+        &self,
+        initiating: &Solution,
+        guiding: &Solution,
+        instance: &TsplibInstance,
+        ls_termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> (Solution, i32) {
+        self.intermediate_solutions(initiating, guiding, instance)
+            .into_iter()
+            .map(|(solution, _)| {
+                let improved = self
+                    .local_search
+                    .solve_from_solution_until(
+                        instance,
+                        solution,
+                        ls_termination,
+                        &mut *progress_callback,
+                    )
+                    .solution;
+                let cost = improved.calculate_cost(instance);
+                (improved, cost)
+            })
+            .min_by_key(|(_, cost)| *cost)
+            .unwrap_or_else(|| (initiating.clone(), initiating.calculate_cost(instance)))
+    }
+}
+
+/// Cheapest move that makes progress towards the guide: exchanges fix the cycle
+/// assignment, insertions add guide edges. Returns the move with its cost delta.
+fn best_relink_move(
+    solution: &Solution,
+    guide_cycle: &[usize],
+    guide_neighbors: &[Vec<usize>],
+    instance: &TsplibInstance,
+) -> Option<(RelinkMove, i32)> {
+    let d = |u: usize, v: usize| instance.distance(u, v);
+    let in_guide = |u: usize, v: usize| guide_neighbors[u].contains(&v);
+    let cycles = [&solution.cycle1, &solution.cycle2];
+    let mut best: Option<(RelinkMove, i32)> = None;
+    let mut consider = |relink_move: RelinkMove, delta: i32| {
+        if best.is_none_or(|(_, best_delta)| delta < best_delta) {
+            best = Some((relink_move, delta));
+        }
+    };
+
+    // Inter-route exchanges of nodes sitting in the wrong cycle
+    let misplaced = |c: usize| -> Vec<usize> {
+        (0..cycles[c].len())
+            .filter(|&i| guide_cycle[cycles[c][i]] != c)
+            .collect()
+    };
+    let (misplaced1, misplaced2) = (misplaced(0), misplaced(1));
+    let replace_delta = |cycle: &[usize], pos: usize, node: usize| {
+        let len = cycle.len();
+        let (prev, old, next) = (
+            cycle[(pos + len - 1) % len],
+            cycle[pos],
+            cycle[(pos + 1) % len],
+        );
+        d(prev, node) + d(node, next) - d(prev, old) - d(old, next)
+    };
+    for &pos1 in &misplaced1 {
+        for &pos2 in &misplaced2 {
+            let delta = replace_delta(cycles[0], pos1, cycles[1][pos2])
+                + replace_delta(cycles[1], pos2, cycles[0][pos1]);
+            consider(RelinkMove::Exchange { pos1, pos2 }, delta);
+        }
+    }
+
+    // Insertions of a node next to a guide neighbour in the same cycle
+    for (c, cycle) in cycles.iter().enumerate() {
+        let len = cycle.len();
+        if len < 4 {
+            continue;
+        }
+        let mut position = vec![usize::MAX; instance.size()];
+        for (i, &v) in cycle.iter().enumerate() {
+            position[v] = i;
+        }
+        for from in 0..len {
+            let b = cycle[from];
+            let (bp, bn) = (cycle[(from + len - 1) % len], cycle[(from + 1) % len]);
+            for &a in &guide_neighbors[b] {
+                let pa = position[a];
+                if pa == usize::MAX || a == bp || a == bn {
+                    continue;
+                }
+                // Insert b after a (between a and its successor) or before a
+                for after in [pa, (pa + len - 1) % len] {
+                    let (x, y) = (cycle[after], cycle[(after + 1) % len]);
+                    if x == b || y == b {
+                        continue;
+                    }
+                    let common_gain = i32::from(in_guide(bp, bn))
+                        + i32::from(in_guide(x, b))
+                        + i32::from(in_guide(b, y))
+                        - i32::from(in_guide(bp, b))
+                        - i32::from(in_guide(b, bn))
+                        - i32::from(in_guide(x, y));
+                    if common_gain <= 0 {
+                        continue;
+                    }
+                    let delta = d(bp, bn) + d(x, b) + d(b, y) - d(bp, b) - d(b, bn) - d(x, y);
+                    consider(
+                        RelinkMove::Insert {
+                            cycle: c,
+                            from,
+                            after,
+                        },
+                        delta,
+                    );
+                }
+            }
+        }
+    }
+
+    best
+}
+
+fn apply_relink_move(solution: &mut Solution, relink_move: RelinkMove) {
+    match relink_move {
+        RelinkMove::Exchange { pos1, pos2 } => {
+            std::mem::swap(&mut solution.cycle1[pos1], &mut solution.cycle2[pos2]);
+        }
+        RelinkMove::Insert { cycle, from, after } => {
+            let cycle = if cycle == 0 {
+                &mut solution.cycle1
+            } else {
+                &mut solution.cycle2
+            };
+            let node = cycle.remove(from);
+            let after = if after > from { after - 1 } else { after };
+            cycle.insert(after + 1, node);
+        }
+    }
+}
+
+impl Recombination for PathRelinking {
+    fn name(&self) -> String {
+        format!("PathRelinking(ls_points={})", self.num_local_searches)
+    }
+
+    /// Relinks from `p1` towards `p2`.
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        _rng: &mut dyn RngCore,
+    ) -> Solution {
+        self.relink(p1, p2, instance, termination, &mut |_| {}).0
+    }
+}
+
+impl BudgetedAlgorithm for PathRelinking {
+    /// Keeps an elite set of local optima and relinks random pairs of its members in
+    /// both directions; results that beat the worst member and are not duplicates of
+    /// an elite solution replace it.
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+        let ls_termination = termination.cancellation_only();
+        let replacement = SimilarityReplacement::new(SimilarityMeasure::CommonEdges, 1.0);
+        let mut replacement_state = ReplacementState::default();
+
+        progress_callback(ProgressEvent::Message(format!(
+            "[{}] Generating elite set...",
+            self.name_str
+        )));
+        let init_ls = LocalSearch::new(
+            self.local_search.variant,
+            self.local_search.neighborhood,
+            InitialSolutionType::Random,
+            self.local_search.verify_delta_computationally,
+        );
+        let mut elite: Vec<(Solution, i32)> = (0..self.elite_size.max(2))
+            .map(|i| {
+                let sol = init_ls
+                    .solve_budgeted(instance, &ls_termination, &mut |event| {
+                        progress_callback(event.nested(format!("Elite {}", i + 1)))
+                    })
+                    .solution;
+                let cost = sol.calculate_cost(instance);
+                progress.record_evaluation(cost);
+                (sol, cost)
+            })
+            .collect();
+        elite.sort_by_key(|(_, cost)| *cost);
+        let mut best_solution = elite[0].0.clone();
+        let mut best_cost = elite[0].1;
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let i = rng.random_range(0..elite.len());
+            let mut j = rng.random_range(0..elite.len() - 1);
+            if j >= i {
+                j += 1;
+            }
+            let mut ls_callback = |event: ProgressEvent| {
+                progress_callback(event.nested(format!("Iter {} LS on path", iterations)))
+            };
+            let forward = self.relink(
+                &elite[i].0,
+                &elite[j].0,
+                instance,
+                &ls_termination,
+                &mut ls_callback,
+            );
+            let backward = self.relink(
+                &elite[j].0,
+                &elite[i].0,
+                instance,
+                &ls_termination,
+                &mut ls_callback,
+            );
+            let (child, child_cost) = if forward.1 <= backward.1 {
+                forward
+            } else {
+                backward
+            };
+            progress.record_evaluation(child_cost);
+
+            if child_cost < best_cost {
+                best_solution = child.clone();
+                best_cost = child_cost;
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost: child_cost,
+                best_cost,
+            });
+
+            if let Some(index) = replacement.replace(
+                &mut elite,
+                child,
+                child_cost,
+                instance,
+                &mut replacement_state,
+                &mut rng,
+            ) {
+                progress_callback(ProgressEvent::PopulationReplaced {
+                    iteration: iterations,
+                    index,
+                    cost: child_cost,
+                });
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for PathRelinking {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -1,18 +1,48 @@
 use crate::algorithms::constructive::weighted_regret_cycle::WeightedRegretCycle;
 use crate::moves::types::{CycleId, Move};
 use crate::tsplib::{Solution, TsplibInstance};
-use rand::Rng;
 use rand::seq::{IndexedMutRandom, SliceRandom};
+use rand::{Rng, RngCore};
 use std::collections::HashSet;
 
-pub trait Perturbation {
+/// Modifies a solution in place to move the search elsewhere. Object safe, so
+/// perturbations can be picked at runtime and stored as `Box<dyn Perturbation>`.
+pub trait Perturbation: Send + Sync {
     fn name(&self) -> String;
-    fn perturb<R: Rng + ?Sized>(
-        &self,
-        solution: &mut Solution,
-        instance: &TsplibInstance,
-        rng: &mut R,
-    );
+    fn perturb(&self, solution: &mut Solution, instance: &TsplibInstance, rng: &mut dyn RngCore);
+}
+
+// --- Composition ---
+
+/// Applies each perturbation the given number of times, in order
+/// (e.g. three double bridges followed by a segment swap).
+pub struct PerturbationSequence {
+    steps: Vec<(Box<dyn Perturbation>, usize)>,
+}
+
+impl PerturbationSequence {
+    pub fn new(steps: Vec<(Box<dyn Perturbation>, usize)>) -> Self {
+        Self { steps }
+    }
+}
+
+impl Perturbation for PerturbationSequence {
+    fn name(&self) -> String {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|(perturbation, times)| format!("{}x {}", times, perturbation.name()))
+            .collect();
+        format!("Sequence[{}]", steps.join(", "))
+    }
+
+    fn perturb(&self, solution: &mut Solution, instance: &TsplibInstance, rng: &mut dyn RngCore) {
+        for (perturbation, times) in &self.steps {
+            for _ in 0..*times {
+                perturbation.perturb(solution, instance, rng);
+            }
+        }
+    }
 }
 
 // --- Small Perturbation (for ILS) ---
@@ -33,12 +63,7 @@ impl Perturbation for SmallPerturbation {
         format!("SmallPerturbation(n_moves={})", self.num_moves)
     }
 
-    fn perturb<R: Rng + ?Sized>(
-        &self,
-        solution: &mut Solution,
-        _instance: &TsplibInstance,
-        rng: &mut R,
-    ) {
+    fn perturb(&self, solution: &mut Solution, _instance: &TsplibInstance, rng: &mut dyn RngCore) {
         for _ in 0..self.num_moves {
             if let Some(random_move) = generate_random_move(solution, rng) {
                 // Apply the move directly without checking delta
@@ -163,6 +188,143 @@ fn generate_random_intra_edge_exchange<R: Rng + ?Sized>(
     })
 }
 
+// --- Structural Perturbations (for ILS) ---
+
+/// Double bridge (4-opt segment reordering) in each cycle: `A B C D -> A C B D`.
+/// A 2-opt local search cannot undo it in one move.
+#[derive(Debug, Clone)]
+pub struct DoubleBridge;
+
+/// Smallest cycle a double bridge is applied to.
+const DOUBLE_BRIDGE_MIN_CYCLE: usize = 8;
+
+impl Perturbation for DoubleBridge {
+    fn name(&self) -> String {
+        "DoubleBridge".to_string()
+    }
+
+    fn perturb(&self, solution: &mut Solution, _instance: &TsplibInstance, rng: &mut dyn RngCore) {
+        for cycle in [&mut solution.cycle1, &mut solution.cycle2] {
+            let n = cycle.len();
+            if n < DOUBLE_BRIDGE_MIN_CYCLE {
+                continue;
+            }
+            let mut cuts = rand::seq::index::sample(rng, n - 1, 3).into_vec();
+            cuts.iter_mut().for_each(|cut| *cut += 1);
+            cuts.sort_unstable();
+            let (p1, p2, p3) = (cuts[0], cuts[1], cuts[2]);
+            let mut reordered = Vec::with_capacity(n);
+            reordered.extend_from_slice(&cycle[..p1]);
+            reordered.extend_from_slice(&cycle[p2..p3]);
+            reordered.extend_from_slice(&cycle[p1..p2]);
+            reordered.extend_from_slice(&cycle[p3..]);
+            *cycle = reordered;
+        }
+    }
+}
+
+/// Exchanges two random segments of equal length between the cycles, each possibly
+/// reversed. Cycle sizes are unchanged.
+#[derive(Debug, Clone)]
+pub struct SegmentSwap {
+    max_segment_length: usize,
+}
+
+impl SegmentSwap {
+    pub fn new(max_segment_length: usize) -> Self {
+        assert!(max_segment_length > 0, "Segment length must be positive");
+        Self { max_segment_length }
+    }
+}
+
+impl Perturbation for SegmentSwap {
+    fn name(&self) -> String {
+        format!("SegmentSwap(max_len={})", self.max_segment_length)
+    }
+
+    fn perturb(&self, solution: &mut Solution, _instance: &TsplibInstance, rng: &mut dyn RngCore) {
+        let (n1, n2) = (solution.cycle1.len(), solution.cycle2.len());
+        // Leave at least one node of each cycle in place
+        let max_length = self.max_segment_length.min(n1.min(n2).saturating_sub(1));
+        if max_length == 0 {
+            return;
+        }
+        let length = rng.random_range(1..=max_length);
+        // Rotating a cycle does not change it, so both segments can start at position 0
+        solution.cycle1.rotate_left(rng.random_range(0..n1));
+        solution.cycle2.rotate_left(rng.random_range(0..n2));
+        for cycle in [&mut solution.cycle1, &mut solution.cycle2] {
+            if rng.random_bool(0.5) {
+                cycle[..length].reverse();
+            }
+        }
+        solution.cycle1[..length].swap_with_slice(&mut solution.cycle2[..length]);
+    }
+}
+
+/// Moves a cluster of nodes around a random centre to the other cycle, and the same number
+/// of the other cycle's nodes closest to that centre back, reinserting all of them with
+/// cheapest insertion. Cycle sizes are unchanged.
+#[derive(Debug, Clone)]
+pub struct ClusterTransfer {
+    cluster_size: usize,
+}
+
+impl ClusterTransfer {
+    pub fn new(cluster_size: usize) -> Self {
+        assert!(cluster_size > 0, "Cluster size must be positive");
+        Self { cluster_size }
+    }
+}
+
+impl Perturbation for ClusterTransfer {
+    fn name(&self) -> String {
+        format!("ClusterTransfer(size={})", self.cluster_size)
+    }
+
+    fn perturb(&self, solution: &mut Solution, instance: &TsplibInstance, rng: &mut dyn RngCore) {
+        let (n1, n2) = (solution.cycle1.len(), solution.cycle2.len());
+        let size = self.cluster_size.min(n1.min(n2) / 2);
+        if size == 0 {
+            return;
+        }
+        let from_cycle1 = rng.random_bool(n1 as f64 / (n1 + n2) as f64);
+        let (source, target) = if from_cycle1 {
+            (&mut solution.cycle1, &mut solution.cycle2)
+        } else {
+            (&mut solution.cycle2, &mut solution.cycle1)
+        };
+        let centre = source[rng.random_range(0..source.len())];
+        let closest = |cycle: &[usize]| -> Vec<usize> {
+            let mut nodes = cycle.to_vec();
+            nodes.sort_unstable_by_key(|&v| instance.distance(centre, v));
+            nodes.truncate(size);
+            nodes
+        };
+        let outgoing = closest(source);
+        let incoming = closest(target);
+        source.retain(|v| !outgoing.contains(v));
+        target.retain(|v| !incoming.contains(v));
+        cheapest_insertion(source, &incoming, instance);
+        cheapest_insertion(target, &outgoing, instance);
+    }
+}
+
+/// Inserts each of `nodes` in turn at its cheapest position in `cycle`.
+fn cheapest_insertion(cycle: &mut Vec<usize>, nodes: &[usize], instance: &TsplibInstance) {
+    for &node in nodes {
+        let n = cycle.len();
+        let position = (0..n)
+            .min_by_key(|&i| {
+                let (prev, next) = (cycle[i], cycle[(i + 1) % n]);
+                instance.distance(prev, node) + instance.distance(node, next)
+                    - instance.distance(prev, next)
+            })
+            .map_or(0, |i| i + 1);
+        cycle.insert(position, node);
+    }
+}
+
 // --- Large Perturbation (for LNS) ---
 
 #[derive(Debug, Clone)]
@@ -186,12 +348,7 @@ impl Perturbation for LargePerturbation {
         format!("LargePerturbation(destroy={:.2})", self.destroy_fraction)
     }
 
-    fn perturb<R: Rng + ?Sized>(
-        &self,
-        solution: &mut Solution,
-        instance: &TsplibInstance,
-        rng: &mut R,
-    ) {
+    fn perturb(&self, solution: &mut Solution, instance: &TsplibInstance, rng: &mut dyn RngCore) {
         let nodes_to_remove_count =
             ((instance.dimension as f64 * self.destroy_fraction) / 2.0).round() as usize * 2;
         if nodes_to_remove_count == 0 {
@@ -1,6 +1,9 @@
 use crate::algorithm::ProgressCallback;
-use crate::algorithm::TspAlgorithm;
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressEvent, SolveOutcome, TspAlgorithm, reports_move_iteration,
+};
 use crate::moves::types::{CycleId, Move};
+use crate::termination::{SearchProgress, StopReason, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 use crate::utils::generate_random_solution;
 use rand::{Rng, thread_rng};
@@ -114,43 +117,84 @@ impl RandomWalk {
     }
 }
 
-impl TspAlgorithm for RandomWalk {
-    fn name(&self) -> &str {
-        "Random Walk"
-    }
-
-    fn solve_with_feedback(
+impl BudgetedAlgorithm for RandomWalk {
+    fn solve_budgeted(
         &self,
         instance: &TsplibInstance,
+        termination: &Termination,
         progress_callback: ProgressCallback,
-    ) -> Solution {
+    ) -> SolveOutcome {
+        let mut progress = SearchProgress::new();
         let mut current_solution = generate_random_solution(instance);
         let mut best_solution = current_solution.clone();
         let mut best_cost = best_solution.calculate_cost(instance);
+        progress.record_evaluation(best_cost);
         let mut rng = thread_rng();
 
-        for i in 0..self.max_iterations {
-            if i % 100 == 0 || i == self.max_iterations - 1 {
-                progress_callback(format!(
-                    "[Iter: {}/{}] Best Cost: {}",
-                    i + 1,
-                    self.max_iterations,
-                    best_cost
-                ));
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            if reports_move_iteration(progress.iterations) {
+                progress_callback(ProgressEvent::IterationStarted {
+                    iteration: progress.iterations,
+                    best_cost,
+                });
             }
 
             if let Some(random_move) = self.generate_random_move(&current_solution, &mut rng) {
                 random_move.apply(&mut current_solution);
                 let current_cost = current_solution.calculate_cost(instance);
-                if current_cost < best_cost {
+                if progress.record_evaluation(current_cost) {
                     best_cost = current_cost;
                     best_solution = current_solution.clone();
+                    progress_callback(ProgressEvent::NewBest {
+                        iteration: progress.iterations,
+                        cost: best_cost,
+                        elapsed: progress.elapsed(),
+                    });
+                }
+                if reports_move_iteration(progress.iterations) {
+                    progress_callback(ProgressEvent::IterationFinished {
+                        iteration: progress.iterations,
+                        current_cost,
+                        best_cost,
+                    });
                 }
             } else {
-                break;
+                // Instance too small to produce any move
+                break StopReason::Converged;
             }
+        };
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
         }
-        progress_callback(format!("[Finished] Final Best Cost: {}", best_cost));
-        best_solution
+    }
+}
+
+impl TspAlgorithm for RandomWalk {
+    fn name(&self) -> &str {
+        "Random Walk"
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        self.solve_budgeted(
+            instance,
+            &Termination::MaxIterations(self.max_iterations),
+            progress_callback,
+        )
+        .solution
     }
 }
@@ -0,0 +1,102 @@
+//! Repair-based recombination of the Enhanced-HAE family.
+
+use super::Recombination;
+use crate::algorithms::perturbation::repair;
+use crate::termination::Termination;
+use crate::tsplib::{CycleId, Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+/// Starts from the better parent, destroys the ends of edges missing from both parents
+/// (and, with a small probability, of any edge), additionally destroys the 10% most
+/// expensive remaining nodes and rebuilds with the weighted-regret `repair`.
+#[derive(Debug, Clone, Copy)]
+pub struct DestroyRepairRecombination {
+    /// Chance of destroying the ends of an edge kept from the parents.
+    pub edge_destroy_probability: f64,
+    /// Node cost proxy: sum of distances to this many nearest neighbours, or to all
+    /// other nodes when `None`.
+    pub node_cost_neighbors: Option<usize>,
+}
+
+impl DestroyRepairRecombination {
+    pub fn new(edge_destroy_probability: f64, node_cost_neighbors: Option<usize>) -> Self {
+        Self {
+            edge_destroy_probability,
+            node_cost_neighbors,
+        }
+    }
+
+    fn node_cost(&self, node: usize, instance: &TsplibInstance) -> i32 {
+        match self.node_cost_neighbors {
+            Some(k) => instance
+                .get_nearest_neighbors(node)
+                .iter()
+                .take(k)
+                .map(|&neighbor| instance.distance(node, neighbor))
+                .sum(),
+            None => (0..instance.dimension)
+                .filter(|&other| other != node)
+                .map(|other| instance.distance(node, other))
+                .sum(),
+        }
+    }
+}
+
+impl Recombination for DestroyRepairRecombination {
+    fn name(&self) -> String {
+        format!(
+            "DestroyRepair(p={}, node_cost_k={:?})",
+            self.edge_destroy_probability, self.node_cost_neighbors
+        )
+    }
+
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        _termination: &Termination,
+        rng: &mut dyn RngCore,
+    ) -> Solution {
+        let mut child = if p1.calculate_cost(instance) < p2.calculate_cost(instance) {
+            p1.clone()
+        } else {
+            p2.clone()
+        };
+
+        let mut destroyed: HashSet<usize> = HashSet::new();
+
+        for &cycle_id in &[CycleId::Cycle1, CycleId::Cycle2] {
+            let cycle = child.get_cycle(cycle_id);
+            let n = cycle.len();
+            for i in 0..n {
+                let a = cycle[i];
+                let b = cycle[(i + 1) % n];
+
+                if (p2.has_edge(a, b).is_none() && p1.has_edge(a, b).is_none())
+                    || rng.random_bool(self.edge_destroy_probability)
+                {
+                    destroyed.insert(a);
+                    destroyed.insert(b);
+                }
+            }
+        }
+
+        let mut node_costs: Vec<(usize, i32)> = (0..instance.dimension)
+            .filter(|node| !destroyed.contains(node))
+            .map(|node| (node, self.node_cost(node, instance)))
+            .collect();
+        node_costs.sort_by_key(|(_, cost)| -*cost);
+
+        let destroy_count = (node_costs.len() as f64 * 0.1).ceil() as usize;
+        destroyed.extend(node_costs.iter().take(destroy_count).map(|&(node, _)| node));
+
+        child.cycle1.retain(|v| !destroyed.contains(v));
+        child.cycle2.retain(|v| !destroyed.contains(v));
+
+        repair(&mut child, instance, destroyed);
+
+        child
+    }
+}
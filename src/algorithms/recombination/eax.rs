@@ -0,0 +1,259 @@
+//! Edge Assembly Crossover (EAX) adapted to solutions made of two disjoint cycles.
+//!
+//! The edges of both parents form a union graph in which every node has two edges of
+//! parent A and two of parent B. Edges present in both parents are left out and the
+//! rest is decomposed into AB-cycles, whose edges alternate between the parents.
+//! Applying an AB-cycle (E-set) to parent A removes its A edges and adds its B edges,
+//! which keeps every node at degree two but may split the solution into subtours.
+//! The subtours are merged back into exactly two cycles with the cheapest 2-opt-style
+//! reconnection, and the cycles are rebalanced with the weighted-regret `repair`.
+
+use super::{Edge, Recombination, cycle_edges, normalized, remove_one, subtours};
+use crate::algorithms::perturbation::repair;
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+/// Children built per recombination, each from a single AB-cycle (EAX-1AB).
+const MAX_CHILDREN: usize = 10;
+
+/// `Recombination` wrapper around [`eax`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EaxRecombination;
+
+impl Recombination for EaxRecombination {
+    fn name(&self) -> String {
+        "EAX".to_string()
+    }
+
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        _termination: &Termination,
+        rng: &mut dyn RngCore,
+    ) -> Solution {
+        eax(p1, p2, instance, rng)
+    }
+}
+
+/// Builds a child from `p1` and `p2` and returns the cheapest of up to `MAX_CHILDREN`
+/// single-AB-cycle offspring. Parents without differing edges yield a copy of `p1`.
+pub fn eax<R: Rng + ?Sized>(
+    p1: &Solution,
+    p2: &Solution,
+    instance: &TsplibInstance,
+    rng: &mut R,
+) -> Solution {
+    let mut ab_cycles = ab_cycles(p1, p2, instance.size(), rng);
+    // Random subset of the AB-cycles without shuffling the whole list
+    let num_children = ab_cycles.len().min(MAX_CHILDREN);
+    for i in 0..num_children {
+        let j = rng.random_range(i..ab_cycles.len());
+        ab_cycles.swap(i, j);
+    }
+
+    ab_cycles[..num_children]
+        .iter()
+        .map(|ab_cycle| {
+            let child = apply_e_set(p1, ab_cycle, instance);
+            let cost = child.calculate_cost(instance);
+            (child, cost)
+        })
+        .min_by_key(|(_, cost)| *cost)
+        .map_or_else(|| p1.clone(), |(child, _)| child)
+}
+
+/// Decomposes the edges in which `a` and `b` differ into AB-cycles.
+///
+/// Each AB-cycle is returned as a node sequence `v0, v1, ...` where `(v0, v1)` is an
+/// edge of `a`, `(v1, v2)` an edge of `b`, and so on around the cycle.
+fn ab_cycles<R: Rng + ?Sized>(
+    a: &Solution,
+    b: &Solution,
+    n: usize,
+    rng: &mut R,
+) -> Vec<Vec<usize>> {
+    let a_edges = cycle_edges(a);
+    let b_edges = cycle_edges(b);
+    let a_set: HashSet<Edge> = a_edges.iter().map(|&(u, v)| normalized(u, v)).collect();
+    let b_set: HashSet<Edge> = b_edges.iter().map(|&(u, v)| normalized(u, v)).collect();
+
+    // adjacency[0]: edges only in `a`, adjacency[1]: edges only in `b`
+    let mut adjacency = [vec![Vec::new(); n], vec![Vec::new(); n]];
+    for (side, edges, other) in [(0, &a_edges, &b_set), (1, &b_edges, &a_set)] {
+        for &(u, v) in edges {
+            if !other.contains(&normalized(u, v)) {
+                adjacency[side][u].push(v);
+                adjacency[side][v].push(u);
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    loop {
+        let starts: Vec<usize> = (0..n).filter(|&v| !adjacency[0][v].is_empty()).collect();
+        if starts.is_empty() {
+            break;
+        }
+        let mut path = vec![starts[rng.random_range(0..starts.len())]];
+
+        // Alternating random walk; closed alternating loops are cut off as they appear
+        loop {
+            let side = (path.len() - 1) % 2;
+            let current = path[path.len() - 1];
+            if adjacency[side][current].is_empty() {
+                break;
+            }
+            let idx = rng.random_range(0..adjacency[side][current].len());
+            let next = adjacency[side][current].swap_remove(idx);
+            remove_one(&mut adjacency[side][next], current);
+            path.push(next);
+
+            let end = path.len() - 1;
+            if let Some(j) = (0..end)
+                .rev()
+                .find(|&j| path[j] == next && (end - j) % 2 == 0)
+            {
+                let mut cycle = path[j..end].to_vec();
+                if j % 2 == 1 {
+                    // Start the cycle with an edge of `a`
+                    cycle.rotate_left(1);
+                }
+                if cycle.len() >= 4 {
+                    cycles.push(cycle);
+                }
+                path.truncate(j + 1);
+            }
+        }
+    }
+    cycles
+}
+
+/// Replaces the `a` edges of `ab_cycle` in `base` with its `b` edges and turns the
+/// resulting subtours back into two balanced cycles.
+fn apply_e_set(base: &Solution, ab_cycle: &[usize], instance: &TsplibInstance) -> Solution {
+    let n = instance.size();
+    let mut adjacency = vec![Vec::with_capacity(2); n];
+    for (u, v) in cycle_edges(base) {
+        adjacency[u].push(v);
+        adjacency[v].push(u);
+    }
+    let m = ab_cycle.len();
+    for i in 0..m {
+        let (u, v) = (ab_cycle[i], ab_cycle[(i + 1) % m]);
+        if i % 2 == 0 {
+            remove_one(&mut adjacency[u], v);
+            remove_one(&mut adjacency[v], u);
+        } else {
+            adjacency[u].push(v);
+            adjacency[v].push(u);
+        }
+    }
+
+    let mut cycles = merge_subtours(subtours(&adjacency), instance);
+    if cycles.len() == 1 {
+        let (cycle1, cycle2) = split_cycle(&cycles[0], instance);
+        cycles = vec![cycle1, cycle2];
+    }
+    cycles.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    let cycle2 = cycles.pop().unwrap_or_default();
+    let cycle1 = cycles.pop().unwrap_or_default();
+    let mut child = Solution::new(cycle1, cycle2);
+    rebalance(&mut child, instance);
+    child
+}
+
+/// Repeatedly merges the smallest subtour into another one until two remain, choosing
+/// the pair of removed edges and reconnection with the lowest cost increase.
+fn merge_subtours(mut subtours: Vec<Vec<usize>>, instance: &TsplibInstance) -> Vec<Vec<usize>> {
+    let d = |u: usize, v: usize| instance.distance(u, v);
+    while subtours.len() > 2 {
+        let smallest = (0..subtours.len())
+            .min_by_key(|&i| subtours[i].len())
+            .unwrap();
+        let s = subtours.swap_remove(smallest);
+
+        // (delta, target subtour, edge index in s, edge index in target, reverse s)
+        let mut best = (i32::MAX, 0, 0, 0, false);
+        for (t_idx, t) in subtours.iter().enumerate() {
+            for i in 0..s.len() {
+                let (a, b) = (s[i], s[(i + 1) % s.len()]);
+                for j in 0..t.len() {
+                    let (c, e) = (t[j], t[(j + 1) % t.len()]);
+                    let removed = d(a, b) + d(c, e);
+                    let straight = d(c, a) + d(b, e) - removed;
+                    let crossed = d(c, b) + d(a, e) - removed;
+                    if straight < best.0 {
+                        best = (straight, t_idx, i, j, true);
+                    }
+                    if crossed < best.0 {
+                        best = (crossed, t_idx, i, j, false);
+                    }
+                }
+            }
+        }
+
+        let (_, t_idx, i, j, reverse) = best;
+        let t = &subtours[t_idx];
+        // Target opened between t[j] and t[j + 1], walked from t[j + 1] round to t[j]
+        let mut merged: Vec<usize> = t[j + 1..].iter().chain(&t[..=j]).copied().collect();
+        // Subtour opened between s[i] and s[i + 1], walked from s[i + 1] round to s[i]
+        let mut s_path: Vec<usize> = s[i + 1..].iter().chain(&s[..=i]).copied().collect();
+        if reverse {
+            s_path.reverse();
+        }
+        merged.extend(s_path);
+        subtours[t_idx] = merged;
+    }
+    subtours
+}
+
+/// Cuts one cycle into two balanced cycles at the cheapest pair of edges.
+fn split_cycle(cycle: &[usize], instance: &TsplibInstance) -> (Vec<usize>, Vec<usize>) {
+    let n = cycle.len();
+    if n < 2 {
+        return (cycle.to_vec(), Vec::new());
+    }
+    let d = |u: usize, v: usize| instance.distance(u, v);
+    let size1 = n.div_ceil(2);
+    let best_start = (0..n)
+        .min_by_key(|&i| {
+            let first = cycle[i];
+            let last = cycle[(i + size1 - 1) % n];
+            let before = cycle[(i + n - 1) % n];
+            let after = cycle[(i + size1) % n];
+            d(first, last) + d(after, before) - d(before, first) - d(last, after)
+        })
+        .unwrap();
+    let rotated: Vec<usize> = cycle[best_start..]
+        .iter()
+        .chain(&cycle[..best_start])
+        .copied()
+        .collect();
+    (rotated[..size1].to_vec(), rotated[size1..].to_vec())
+}
+
+/// Moves the nodes cheapest to remove out of the larger `cycle1` until it has its
+/// target size, and reinserts them into `cycle2` with the weighted-regret `repair`.
+fn rebalance(solution: &mut Solution, instance: &TsplibInstance) {
+    let target1 = instance.size().div_ceil(2);
+    let mut removed = HashSet::new();
+    while solution.cycle1.len() > target1 {
+        let cycle = &solution.cycle1;
+        let n = cycle.len();
+        let pos = (0..n)
+            .max_by_key(|&i| {
+                let (prev, node, next) = (cycle[(i + n - 1) % n], cycle[i], cycle[(i + 1) % n]);
+                instance.distance(prev, node) + instance.distance(node, next)
+                    - instance.distance(prev, next)
+            })
+            .unwrap();
+        removed.insert(solution.cycle1.remove(pos));
+    }
+    if !removed.is_empty() {
+        repair(solution, instance, removed);
+    }
+}
@@ -0,0 +1,66 @@
+//! Lab-6 recombination: keeps the edges shared by both parents.
+
+use super::{Edge, Recombination, cycle_edges, normalized};
+use crate::algorithms::perturbation::repair;
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+
+/// Starts from one parent, keeps the edges it shares with the other, removes every
+/// node not incident to a common edge and rebuilds the removed part with the
+/// weighted-regret `repair`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EdgePreservingRecombination;
+
+impl Recombination for EdgePreservingRecombination {
+    fn name(&self) -> String {
+        "EdgePreserving".to_string()
+    }
+
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        _termination: &Termination,
+        rng: &mut dyn RngCore,
+    ) -> Solution {
+        let (base, other) = if rng.random_bool(0.5) {
+            (p1, p2)
+        } else {
+            (p2, p1)
+        };
+
+        // Undirected edges of the other parent, regardless of which cycle holds them
+        let other_edges: HashSet<Edge> = cycle_edges(other)
+            .into_iter()
+            .map(|(u, v)| normalized(u, v))
+            .collect();
+        let is_common = |u: usize, v: usize| other_edges.contains(&normalized(u, v));
+
+        let mut destroyed: HashSet<usize> = HashSet::new();
+        for cycle in [&base.cycle1, &base.cycle2] {
+            let n = cycle.len();
+            if n < 2 {
+                destroyed.extend(cycle.iter().copied());
+                continue;
+            }
+            for i in 0..n {
+                let prev = cycle[(i + n - 1) % n];
+                let node = cycle[i];
+                let next = cycle[(i + 1) % n];
+                if !is_common(prev, node) && !is_common(node, next) {
+                    destroyed.insert(node);
+                }
+            }
+        }
+
+        let mut child = base.clone();
+        child.cycle1.retain(|v| !destroyed.contains(v));
+        child.cycle2.retain(|v| !destroyed.contains(v));
+        repair(&mut child, instance, destroyed);
+
+        child
+    }
+}
@@ -0,0 +1,291 @@
+//! Generalized Partition Crossover (GPX) for solutions made of two disjoint cycles.
+//!
+//! Removing the edges shared by both parents splits their union graph into
+//! components. A component can be inherited independently when both parents enter and
+//! leave it the same way: their edges inside the component form paths joining the
+//! same pairs of nodes and covering the same number of nodes, plus closed cycles of
+//! the same sizes. For every such component the child takes the cheaper parent's
+//! edges; everything else comes from the better parent, so the child is never worse
+//! than the better parent and the cycle sizes are preserved.
+
+use super::{Edge, Recombination, cycle_edges, normalized, subtours};
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::RngCore;
+use std::collections::{HashMap, HashSet};
+
+/// Paths as `(end, end, node count)` and closed cycle sizes, both sorted.
+type ComponentShape = (Vec<(usize, usize, usize)>, Vec<usize>);
+
+/// `Recombination` wrapper around [`gpx`]; ignores the random number generator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GpxRecombination;
+
+impl Recombination for GpxRecombination {
+    fn name(&self) -> String {
+        "GPX".to_string()
+    }
+
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        _termination: &Termination,
+        _rng: &mut dyn RngCore,
+    ) -> Solution {
+        gpx(p1, p2, instance)
+    }
+}
+
+/// Deterministic, respectful child of `p1` and `p2`.
+pub fn gpx(p1: &Solution, p2: &Solution, instance: &TsplibInstance) -> Solution {
+    let (a, b) = if p1.calculate_cost(instance) <= p2.calculate_cost(instance) {
+        (p1, p2)
+    } else {
+        (p2, p1)
+    };
+    let n = instance.size();
+    let a_edges = cycle_edges(a);
+    let b_edges = cycle_edges(b);
+    let a_set: HashSet<Edge> = a_edges.iter().map(|&(u, v)| normalized(u, v)).collect();
+    let b_set: HashSet<Edge> = b_edges.iter().map(|&(u, v)| normalized(u, v)).collect();
+
+    // Components of the union graph without the common edges
+    let mut root: Vec<usize> = (0..n).collect();
+    fn find(root: &mut [usize], mut v: usize) -> usize {
+        while root[v] != v {
+            root[v] = root[root[v]];
+            v = root[v];
+        }
+        v
+    }
+    let mut in_partition = vec![false; n];
+    for &(u, v) in a_edges
+        .iter()
+        .filter(|&&(u, v)| !b_set.contains(&normalized(u, v)))
+        .chain(
+            b_edges
+                .iter()
+                .filter(|&&(u, v)| !a_set.contains(&normalized(u, v))),
+        )
+    {
+        in_partition[u] = true;
+        in_partition[v] = true;
+        let (ru, rv) = (find(&mut root, u), find(&mut root, v));
+        root[ru] = rv;
+    }
+    let component: Vec<Option<usize>> = (0..n)
+        .map(|v| in_partition[v].then(|| find(&mut root, v)))
+        .collect();
+    let inside = |&(u, v): &Edge| component[u].is_some() && component[u] == component[v];
+
+    let mut a_inside: HashMap<usize, Vec<Edge>> = HashMap::new();
+    for &edge in a_edges.iter().filter(|e| inside(e)) {
+        a_inside
+            .entry(component[edge.0].unwrap())
+            .or_default()
+            .push(edge);
+    }
+    let mut b_inside: HashMap<usize, Vec<Edge>> = HashMap::new();
+    for &edge in b_edges.iter().filter(|e| inside(e)) {
+        b_inside
+            .entry(component[edge.0].unwrap())
+            .or_default()
+            .push(edge);
+    }
+
+    let edges_cost =
+        |edges: &[Edge]| -> i32 { edges.iter().map(|&(u, v)| instance.distance(u, v)).sum() };
+    let from_b: HashSet<usize> = a_inside
+        .iter()
+        .filter(|(id, a_part)| {
+            let b_part = b_inside.get(id).map_or(&[][..], Vec::as_slice);
+            edges_cost(b_part) < edges_cost(a_part)
+                && component_shape(a_part) == component_shape(b_part)
+        })
+        .map(|(&id, _)| id)
+        .collect();
+    if from_b.is_empty() {
+        return a.clone();
+    }
+
+    let taken_from =
+        |edge: &Edge| component[edge.0].filter(|id| inside(edge) && from_b.contains(id));
+    let mut adjacency = vec![Vec::with_capacity(2); n];
+    for &(u, v) in a_edges
+        .iter()
+        .filter(|e| taken_from(e).is_none())
+        .chain(b_edges.iter().filter(|e| taken_from(e).is_some()))
+    {
+        adjacency[u].push(v);
+        adjacency[v].push(u);
+    }
+
+    let mut cycles = subtours(&adjacency);
+    if cycles.len() != 2 || adjacency.iter().any(|neighbors| neighbors.len() != 2) {
+        // Only reachable with degenerate parents (cycles of fewer than three nodes)
+        return a.clone();
+    }
+    let cycle2 = cycles.pop().unwrap();
+    let cycle1 = cycles.pop().unwrap();
+    Solution::new(cycle1, cycle2)
+}
+
+/// How a parent's edges inside one component connect its nodes.
+fn component_shape(edges: &[Edge]) -> ComponentShape {
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(u, v) in edges {
+        adjacency.entry(u).or_default().push(v);
+        adjacency.entry(v).or_default().push(u);
+    }
+
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut paths = Vec::new();
+    let mut ends: Vec<usize> = adjacency
+        .iter()
+        .filter(|(_, neighbors)| neighbors.len() == 1)
+        .map(|(&v, _)| v)
+        .collect();
+    ends.sort_unstable();
+    for start in ends {
+        if visited.contains(&start) {
+            continue;
+        }
+        visited.insert(start);
+        let (mut prev, mut current, mut count) = (start, adjacency[&start][0], 1);
+        loop {
+            visited.insert(current);
+            count += 1;
+            match adjacency[&current].iter().find(|&&next| next != prev) {
+                Some(&next) if adjacency[&current].len() == 2 => {
+                    prev = current;
+                    current = next;
+                }
+                _ => break,
+            }
+        }
+        paths.push((start.min(current), start.max(current), count));
+    }
+    paths.sort_unstable();
+
+    // Whatever is left forms closed cycles
+    let mut cycle_sizes = Vec::new();
+    let mut remaining: Vec<usize> = adjacency
+        .keys()
+        .copied()
+        .filter(|v| !visited.contains(v))
+        .collect();
+    remaining.sort_unstable();
+    for start in remaining {
+        if visited.contains(&start) {
+            continue;
+        }
+        let mut stack = vec![start];
+        let mut size = 0;
+        visited.insert(start);
+        while let Some(v) = stack.pop() {
+            size += 1;
+            for &next in &adjacency[&v] {
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        cycle_sizes.push(size);
+    }
+    cycle_sizes.sort_unstable();
+
+    (paths, cycle_sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tsplib::CycleId;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    fn random_instance(n: usize, rng: &mut StdRng) -> TsplibInstance {
+        let coordinates = (0..n)
+            .map(|_| (rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect();
+        TsplibInstance::from_coordinates("random", coordinates)
+    }
+
+    fn random_solution(n: usize, rng: &mut StdRng) -> Solution {
+        let mut nodes: Vec<usize> = (0..n).collect();
+        nodes.shuffle(rng);
+        let cycle2 = nodes.split_off(n.div_ceil(2));
+        Solution::new(nodes, cycle2)
+    }
+
+    fn assert_valid_balanced(child: &Solution, instance: &TsplibInstance) {
+        let n = instance.size();
+        assert!(child.is_valid(instance));
+        let mut sizes = [child.cycle1.len(), child.cycle2.len()];
+        sizes.sort_unstable();
+        assert_eq!(sizes, [n / 2, n.div_ceil(2)]);
+    }
+
+    /// Positions `p` such that swapping the nodes at `p` and `p + 1` of a cycle of `len`
+    /// nodes forms its own partition component: every swap touches the four nodes
+    /// `p - 1 ..= p + 2`, and these groups are disjoint.
+    fn swap_positions(len: usize) -> impl Iterator<Item = usize> {
+        (1..len).step_by(5).filter(move |&p| p + 2 < len)
+    }
+
+    #[test]
+    fn child_takes_the_cheaper_side_of_every_component_of_a_full_partition() {
+        let mut rng = StdRng::seed_from_u64(33);
+        for n in [20, 21, 40, 41] {
+            for _ in 0..10 {
+                let instance = random_instance(n, &mut rng);
+                let p1 = random_solution(n, &mut rng);
+                let p1_cost = p1.calculate_cost(&instance);
+
+                // Every adjacent swap changes p1 inside one component only, so the best
+                // child combines the improving swaps and skips the worsening ones
+                let mut p2 = p1.clone();
+                let mut expected = p1_cost;
+                for cycle in [CycleId::Cycle1, CycleId::Cycle2] {
+                    let len = p1.get_cycle(cycle).len();
+                    for p in swap_positions(len) {
+                        let mut single = p1.clone();
+                        single.get_cycle_mut(cycle).swap(p, p + 1);
+                        expected += (single.calculate_cost(&instance) - p1_cost).min(0);
+                        p2.get_cycle_mut(cycle).swap(p, p + 1);
+                    }
+                }
+                let p2_cost = p2.calculate_cost(&instance);
+
+                for (first, second) in [(&p1, &p2), (&p2, &p1)] {
+                    let child = gpx(first, second, &instance);
+                    let child_cost = child.calculate_cost(&instance);
+                    assert_valid_balanced(&child, &instance);
+                    assert!(child_cost <= p1_cost.min(p2_cost));
+                    assert_eq!(child_cost, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn child_is_never_worse_than_the_better_parent() {
+        let mut rng = StdRng::seed_from_u64(34);
+        for n in [10, 25, 50] {
+            for _ in 0..20 {
+                let instance = random_instance(n, &mut rng);
+                let p1 = random_solution(n, &mut rng);
+                let p2 = random_solution(n, &mut rng);
+                let better_cost = p1
+                    .calculate_cost(&instance)
+                    .min(p2.calculate_cost(&instance));
+                let child = gpx(&p1, &p2, &instance);
+                assert_valid_balanced(&child, &instance);
+                assert!(child.calculate_cost(&instance) <= better_cost);
+            }
+        }
+    }
+}
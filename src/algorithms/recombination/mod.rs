@@ -0,0 +1,110 @@
+pub mod destroy_repair;
+pub mod eax;
+pub mod edge_preserving;
+pub mod gpx;
+
+use crate::algorithms::local_search::base::LocalSearch;
+use crate::algorithms::path_relinking::PathRelinking;
+use crate::termination::Termination;
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::RngCore;
+
+/// Builds a child solution from two parents.
+pub trait Recombination: Send + Sync {
+    fn name(&self) -> String;
+    /// Operators that run local searches stop them once `termination` is met.
+    fn recombine(
+        &self,
+        p1: &Solution,
+        p2: &Solution,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        rng: &mut dyn RngCore,
+    ) -> Solution;
+}
+
+/// Crossover used to build a child from two parents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecombinationType {
+    /// Keep the edges common to both parents, drop nodes outside them and repair.
+    EdgePreserving,
+    /// Edge assembly crossover on the AB-cycles of the two parents.
+    Eax,
+    /// Generalized partition crossover; deterministic and never worse than the better parent.
+    Gpx,
+    /// Walk from the first parent towards the second and keep the best local optimum on the path.
+    PathRelinking,
+}
+
+impl RecombinationType {
+    /// Operators that improve their children (path relinking) use `local_search`.
+    pub fn operator(self, local_search: &LocalSearch) -> Box<dyn Recombination> {
+        match self {
+            RecombinationType::EdgePreserving => {
+                Box::new(edge_preserving::EdgePreservingRecombination)
+            }
+            RecombinationType::Eax => Box::new(eax::EaxRecombination),
+            RecombinationType::Gpx => Box::new(gpx::GpxRecombination),
+            RecombinationType::PathRelinking => {
+                Box::new(PathRelinking::new(local_search.clone(), 0, 1))
+            }
+        }
+    }
+}
+
+/// Undirected edge with the smaller node first.
+type Edge = (usize, usize);
+
+fn normalized(u: usize, v: usize) -> Edge {
+    (u.min(v), u.max(v))
+}
+
+/// Edges of both cycles of `solution`, in cycle order.
+fn cycle_edges(solution: &Solution) -> Vec<Edge> {
+    let mut edges = Vec::with_capacity(solution.cycle1.len() + solution.cycle2.len());
+    for cycle in [&solution.cycle1, &solution.cycle2] {
+        let n = cycle.len();
+        if n < 2 {
+            continue;
+        }
+        for i in 0..n {
+            edges.push((cycle[i], cycle[(i + 1) % n]));
+        }
+    }
+    edges
+}
+
+fn remove_one(neighbors: &mut Vec<usize>, node: usize) {
+    if let Some(pos) = neighbors.iter().position(|&v| v == node) {
+        neighbors.swap_remove(pos);
+    }
+}
+
+/// Cycles of a graph in which every node has two neighbours (or none).
+fn subtours(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; adjacency.len()];
+    let mut subtours = Vec::new();
+    for start in 0..adjacency.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut tour = vec![start];
+        if let Some(&first) = adjacency[start].first() {
+            let (mut prev, mut current) = (start, first);
+            while current != start {
+                visited[current] = true;
+                tour.push(current);
+                let next = if adjacency[current][0] != prev {
+                    adjacency[current][0]
+                } else {
+                    adjacency[current][1]
+                };
+                prev = current;
+                current = next;
+            }
+        }
+        subtours.push(tour);
+    }
+    subtours
+}
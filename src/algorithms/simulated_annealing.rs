@@ -0,0 +1,280 @@
+//! Simulated annealing over random, delta-evaluated moves.
+//!
+//! Every iteration draws one random move; improving moves are always applied and worsening
+//! ones with the Metropolis probability `exp(-delta / T)`. The temperature is lowered after
+//! every epoch and raised again (reheating) when the best solution stagnates.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+    reports_move_iteration,
+};
+use crate::algorithms::local_search::base::{
+    InitialSolutionType, NeighborhoodType, generate_initial_solution,
+};
+use crate::moves::random::random_evaluated_move;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+use std::time::Duration;
+
+/// Acceptance probability of an average worsening move at the calibrated initial temperature.
+const INITIAL_ACCEPTANCE: f64 = 0.8;
+/// Number of random moves sampled to calibrate the initial temperature.
+const CALIBRATION_SAMPLES: usize = 500;
+/// Fraction of the initial temperature restored on reheating.
+const REHEAT_FRACTION: f64 = 0.5;
+/// Bounds of the per-epoch cooling factor of the adaptive schedule.
+const ADAPTIVE_COOLING_BOUNDS: (f64, f64) = (0.5, 0.995);
+
+/// How the temperature is lowered after each epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoolingSchedule {
+    /// `T <- alpha * T`.
+    Geometric { alpha: f64 },
+    /// `T <- T * exp(-lambda * T / sigma)`, where `sigma` is the standard deviation of the
+    /// current cost over the epoch: cools slowly while the cost still fluctuates a lot.
+    Adaptive { lambda: f64 },
+}
+
+pub struct SimulatedAnnealing {
+    /// Intra-route moves: `VertexExchange` or `EdgeExchange`.
+    neighborhood: NeighborhoodType,
+    cooling: CoolingSchedule,
+    /// Starting temperature; calibrated from random moves of the initial solution if `None`.
+    initial_temperature: Option<f64>,
+    /// Number of moves between temperature updates.
+    epoch_length: usize,
+    /// Reheat after this many epochs without a new best solution.
+    reheat_after_epochs: Option<usize>,
+    initial_solution_type: InitialSolutionType,
+    name_str: String,
+}
+
+impl SimulatedAnnealing {
+    pub fn new(
+        neighborhood: NeighborhoodType,
+        cooling: CoolingSchedule,
+        initial_temperature: Option<f64>,
+        epoch_length: usize,
+        reheat_after_epochs: Option<usize>,
+        initial_solution_type: InitialSolutionType,
+    ) -> Self {
+        assert!(
+            matches!(
+                neighborhood,
+                NeighborhoodType::VertexExchange | NeighborhoodType::EdgeExchange
+            ),
+            "Simulated annealing supports only vertex and edge exchange neighborhoods"
+        );
+        assert!(
+            initial_temperature.is_none_or(|t| t > 0.0),
+            "Initial temperature must be positive"
+        );
+        match cooling {
+            CoolingSchedule::Geometric { alpha } => assert!(
+                alpha > 0.0 && alpha < 1.0,
+                "Geometric cooling factor must be within (0, 1)"
+            ),
+            CoolingSchedule::Adaptive { lambda } => assert!(
+                lambda > 0.0 && lambda <= 1.0,
+                "Adaptive cooling rate must be within (0, 1]"
+            ),
+        }
+        let t0 = match initial_temperature {
+            Some(t) => format!("{:.1}", t),
+            None => "auto".to_string(),
+        };
+        let reheat = match reheat_after_epochs {
+            Some(epochs) => format!("{} epochs", epochs),
+            None => "off".to_string(),
+        };
+        let name_str = format!(
+            "Simulated Annealing ({:?}, {:?}, T0={}, epoch={}, reheat={}, init={:?})",
+            neighborhood, cooling, t0, epoch_length, reheat, initial_solution_type
+        );
+        Self {
+            neighborhood,
+            cooling,
+            initial_temperature,
+            epoch_length: epoch_length.max(1),
+            reheat_after_epochs,
+            initial_solution_type,
+            name_str,
+        }
+    }
+
+    /// Temperature at which an average worsening move of `solution` is accepted with
+    /// probability `INITIAL_ACCEPTANCE`. The sampled moves count as evaluations of `progress`.
+    fn calibrate_temperature<R: Rng + ?Sized>(
+        &self,
+        solution: &Solution,
+        instance: &TsplibInstance,
+        progress: &mut SearchProgress,
+        rng: &mut R,
+    ) -> f64 {
+        let worsening: Vec<i32> = (0..CALIBRATION_SAMPLES)
+            .filter_map(|_| random_evaluated_move(solution, instance, self.neighborhood, rng))
+            .inspect(|_| progress.evaluations += 1)
+            .map(|m| m.delta)
+            .filter(|&delta| delta > 0)
+            .collect();
+        if worsening.is_empty() {
+            return 1.0;
+        }
+        let mean = worsening.iter().map(|&d| d as f64).sum::<f64>() / worsening.len() as f64;
+        -mean / INITIAL_ACCEPTANCE.ln()
+    }
+
+    fn cool(&self, temperature: f64, epoch_costs: &[i32]) -> f64 {
+        match self.cooling {
+            CoolingSchedule::Geometric { alpha } => temperature * alpha,
+            CoolingSchedule::Adaptive { lambda } => {
+                let n = epoch_costs.len().max(1) as f64;
+                let mean = epoch_costs.iter().map(|&c| c as f64).sum::<f64>() / n;
+                let variance = epoch_costs
+                    .iter()
+                    .map(|&c| (c as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / n;
+                let (min_factor, max_factor) = ADAPTIVE_COOLING_BOUNDS;
+                let factor = if variance > 0.0 {
+                    (-lambda * temperature / variance.sqrt()).exp()
+                } else {
+                    min_factor
+                };
+                temperature * factor.clamp(min_factor, max_factor)
+            }
+        }
+    }
+}
+
+impl BudgetedAlgorithm for SimulatedAnnealing {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+
+        let mut current_solution = generate_initial_solution(instance, self.initial_solution_type);
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress.record_evaluation(current_cost);
+
+        let initial_temperature = self
+            .initial_temperature
+            .unwrap_or_else(|| {
+                self.calibrate_temperature(&current_solution, instance, &mut progress, &mut rng)
+            });
+        progress_callback(ProgressEvent::Message(format!(
+            "[{}] Initial temperature: {:.2}",
+            self.name_str, initial_temperature
+        )));
+        let mut temperature = initial_temperature;
+        let mut epoch_costs = Vec::with_capacity(self.epoch_length);
+        let mut epochs_without_improvement = 0;
+        let mut improved_in_epoch = false;
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationStarted {
+                    iteration: iterations,
+                    best_cost,
+                });
+            }
+
+            if let Some(candidate) =
+                random_evaluated_move(&current_solution, instance, self.neighborhood, &mut rng)
+            {
+                // Rejected moves are worsening, so counting them cannot move the best cost
+                progress.record_evaluation(current_cost + candidate.delta);
+                let accept = candidate.delta <= 0
+                    || rng.random_bool((-(candidate.delta as f64) / temperature).exp());
+                if accept {
+                    candidate.move_type.apply(&mut current_solution);
+                    current_cost += candidate.delta;
+                    if current_cost < best_cost {
+                        best_cost = current_cost;
+                        best_solution = current_solution.clone();
+                        improved_in_epoch = true;
+                        progress_callback(ProgressEvent::NewBest {
+                            iteration: iterations,
+                            cost: best_cost,
+                            elapsed: progress.elapsed(),
+                        });
+                    }
+                }
+            }
+            epoch_costs.push(current_cost);
+
+            if epoch_costs.len() >= self.epoch_length {
+                temperature = self.cool(temperature, &epoch_costs);
+                epoch_costs.clear();
+                if improved_in_epoch {
+                    epochs_without_improvement = 0;
+                } else {
+                    epochs_without_improvement += 1;
+                }
+                improved_in_epoch = false;
+                if self
+                    .reheat_after_epochs
+                    .is_some_and(|limit| epochs_without_improvement >= limit)
+                {
+                    temperature = initial_temperature * REHEAT_FRACTION;
+                    epochs_without_improvement = 0;
+                    progress_callback(ProgressEvent::Message(format!(
+                        "Iter {}: reheating to {:.2}",
+                        iterations, temperature
+                    )));
+                }
+            }
+
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationFinished {
+                    iteration: iterations,
+                    current_cost,
+                    best_cost,
+                });
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for SimulatedAnnealing {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -0,0 +1,467 @@
+//! Tabu search over inter-route exchanges and intra-route vertex or edge exchanges.
+//!
+//! Every iteration applies the best admissible move, even when it worsens the solution.
+//! A move is admissible if none of its attributes is tabu, or if it leads to a new best
+//! solution (aspiration by objective).
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+};
+use crate::algorithms::local_search::base::{
+    InitialSolutionType, NeighborhoodType, generate_initial_solution,
+};
+use crate::moves::inter_route::evaluate_inter_route_exchange;
+use crate::moves::intra_route::{
+    evaluate_candidate_intra_route_edge_exchange, evaluate_intra_route_edge_exchange,
+    evaluate_intra_route_vertex_exchange,
+};
+use crate::moves::types::{CycleId, EvaluatedMove, Move};
+use crate::termination::{SearchProgress, StopReason, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+type Edge = (usize, usize);
+
+fn normalized(u: usize, v: usize) -> Edge {
+    (u.min(v), u.max(v))
+}
+
+/// What a tabu list remembers about applied moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabuAttribute {
+    /// Edges removed by a move may not be added back while tabu.
+    RemovedEdges,
+    /// Vertices moved by a move may not be moved again while tabu.
+    MovedVertices,
+}
+
+/// Edges and vertices touched by a move.
+struct MoveAttributes {
+    removed: Vec<Edge>,
+    added: Vec<Edge>,
+    moved: Vec<usize>,
+}
+
+/// Expiry iterations of the tabu attributes.
+struct TabuList {
+    attribute: TabuAttribute,
+    edges: HashMap<Edge, usize>,
+    vertices: Vec<usize>,
+}
+
+impl TabuList {
+    fn new(attribute: TabuAttribute, dimension: usize) -> Self {
+        Self {
+            attribute,
+            edges: HashMap::new(),
+            vertices: vec![0; dimension],
+        }
+    }
+
+    fn is_tabu(&self, attributes: &MoveAttributes, iteration: usize) -> bool {
+        match self.attribute {
+            TabuAttribute::RemovedEdges => attributes.added.iter().any(|edge| {
+                self.edges
+                    .get(edge)
+                    .is_some_and(|&expiry| expiry > iteration)
+            }),
+            TabuAttribute::MovedVertices => attributes
+                .moved
+                .iter()
+                .any(|&v| self.vertices[v] > iteration),
+        }
+    }
+
+    fn record(&mut self, attributes: &MoveAttributes, iteration: usize, tenure: usize) {
+        let expiry = iteration + tenure + 1;
+        match self.attribute {
+            TabuAttribute::RemovedEdges => {
+                self.edges.retain(|_, &mut e| e > iteration);
+                for &edge in &attributes.removed {
+                    self.edges.insert(edge, expiry);
+                }
+            }
+            TabuAttribute::MovedVertices => {
+                for &v in &attributes.moved {
+                    self.vertices[v] = expiry;
+                }
+            }
+        }
+    }
+}
+
+pub struct TabuSearch {
+    /// Intra-route moves: `VertexExchange` or `EdgeExchange`.
+    neighborhood: NeighborhoodType,
+    attribute: TabuAttribute,
+    /// Each recorded attribute stays tabu for a tenure drawn from this range.
+    tenure: RangeInclusive<usize>,
+    /// Only evaluate moves that introduce an edge to one of the `k` nearest neighbours.
+    /// Requires `precompute_nearest_neighbors` on the instance.
+    candidate_neighbors: Option<usize>,
+    initial_solution_type: InitialSolutionType,
+    name_str: String,
+}
+
+impl TabuSearch {
+    pub fn new(
+        neighborhood: NeighborhoodType,
+        attribute: TabuAttribute,
+        tenure: RangeInclusive<usize>,
+        candidate_neighbors: Option<usize>,
+        initial_solution_type: InitialSolutionType,
+    ) -> Self {
+        assert!(
+            matches!(
+                neighborhood,
+                NeighborhoodType::VertexExchange | NeighborhoodType::EdgeExchange
+            ),
+            "Tabu search supports only vertex and edge exchange neighborhoods"
+        );
+        assert!(!tenure.is_empty(), "Tabu tenure range must not be empty");
+        let candidates = match candidate_neighbors {
+            Some(k) => format!("knn={}", k),
+            None => "full".to_string(),
+        };
+        let name_str = format!(
+            "Tabu Search ({:?}, {:?}, tenure={}..={}, {}, init={:?})",
+            neighborhood,
+            attribute,
+            tenure.start(),
+            tenure.end(),
+            candidates,
+            initial_solution_type
+        );
+        Self {
+            neighborhood,
+            attribute,
+            tenure,
+            candidate_neighbors,
+            initial_solution_type,
+            name_str,
+        }
+    }
+
+    /// All evaluated moves of the neighbourhood, or only the candidate-list moves.
+    fn generate_moves(
+        &self,
+        instance: &TsplibInstance,
+        solution: &Solution,
+        positions: &[(CycleId, usize)],
+    ) -> Vec<EvaluatedMove> {
+        match self.candidate_neighbors {
+            Some(k) => self.generate_candidate_moves(instance, solution, positions, k),
+            None => self.generate_all_moves(instance, solution),
+        }
+    }
+
+    fn generate_all_moves(
+        &self,
+        instance: &TsplibInstance,
+        solution: &Solution,
+    ) -> Vec<EvaluatedMove> {
+        let mut moves = Vec::new();
+        for pos1 in 0..solution.cycle1.len() {
+            for pos2 in 0..solution.cycle2.len() {
+                moves.extend(evaluate_inter_route_exchange(
+                    solution, instance, pos1, pos2,
+                ));
+            }
+        }
+        for cycle in [CycleId::Cycle1, CycleId::Cycle2] {
+            let n = solution.get_cycle(cycle).len();
+            for pos1 in 0..n {
+                for pos2 in pos1 + 1..n {
+                    moves.extend(self.evaluate_intra(solution, instance, cycle, pos1, pos2));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Moves that make `a` adjacent to one of its `k` nearest neighbours `b`.
+    fn generate_candidate_moves(
+        &self,
+        instance: &TsplibInstance,
+        solution: &Solution,
+        positions: &[(CycleId, usize)],
+        k: usize,
+    ) -> Vec<EvaluatedMove> {
+        let mut moves = Vec::new();
+        for a in 0..instance.dimension {
+            let (cycle_a, pos_a) = positions[a];
+            let len_a = solution.get_cycle(cycle_a).len();
+            for &b in instance.get_nearest_neighbors(a).iter().take(k) {
+                let (cycle_b, pos_b) = positions[b];
+                let len_b = solution.get_cycle(cycle_b).len();
+                if cycle_a != cycle_b {
+                    // Swap `a` with a neighbour of `b`, so that `a` lands next to `b`
+                    for pos in [(pos_b + len_b - 1) % len_b, (pos_b + 1) % len_b] {
+                        let (pos1, pos2) = if cycle_a == CycleId::Cycle1 {
+                            (pos_a, pos)
+                        } else {
+                            (pos, pos_a)
+                        };
+                        moves.extend(evaluate_inter_route_exchange(
+                            solution, instance, pos1, pos2,
+                        ));
+                    }
+                } else {
+                    match self.neighborhood {
+                        NeighborhoodType::EdgeExchange => {
+                            // Adds (a, b) together with (a_next, b_next) or (a_prev, b_prev)
+                            let pos_a_prev = (pos_a + len_a - 1) % len_a;
+                            let pos_b_prev = (pos_b + len_a - 1) % len_a;
+                            moves.extend(evaluate_candidate_intra_route_edge_exchange(
+                                solution, instance, cycle_a, pos_a, pos_b,
+                            ));
+                            moves.extend(evaluate_candidate_intra_route_edge_exchange(
+                                solution, instance, cycle_a, pos_a_prev, pos_b_prev,
+                            ));
+                        }
+                        _ => {
+                            // Move `b` into a position next to `a`
+                            for pos in [(pos_a + len_a - 1) % len_a, (pos_a + 1) % len_a] {
+                                if pos != pos_b {
+                                    moves.extend(evaluate_intra_route_vertex_exchange(
+                                        solution, instance, cycle_a, pos, pos_b,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    fn evaluate_intra(
+        &self,
+        solution: &Solution,
+        instance: &TsplibInstance,
+        cycle: CycleId,
+        pos1: usize,
+        pos2: usize,
+    ) -> Option<EvaluatedMove> {
+        match self.neighborhood {
+            NeighborhoodType::EdgeExchange => {
+                evaluate_intra_route_edge_exchange(solution, instance, cycle, pos1, pos2)
+            }
+            _ => evaluate_intra_route_vertex_exchange(solution, instance, cycle, pos1, pos2),
+        }
+    }
+}
+
+/// Best admissible move: not tabu, or better than the best solution found so far.
+/// Falls back to the best move overall when every move is tabu.
+fn select_move(
+    moves: Vec<EvaluatedMove>,
+    solution: &Solution,
+    positions: &[(CycleId, usize)],
+    tabu_list: &TabuList,
+    iteration: usize,
+    current_cost: i32,
+    best_cost: i32,
+) -> Option<(EvaluatedMove, MoveAttributes)> {
+    let mut sorted = moves;
+    sorted.sort_unstable_by_key(|m| m.delta);
+    let mut fallback = None;
+    for evaluated_move in sorted {
+        let attributes = move_attributes(&evaluated_move.move_type, solution, positions);
+        if attributes.removed.is_empty() {
+            // The move does not change the solution
+            continue;
+        }
+        let aspiration = current_cost + evaluated_move.delta < best_cost;
+        if aspiration || !tabu_list.is_tabu(&attributes, iteration) {
+            return Some((evaluated_move, attributes));
+        }
+        if fallback.is_none() {
+            fallback = Some((evaluated_move, attributes));
+        }
+    }
+    fallback
+}
+
+fn node_positions(solution: &Solution, dimension: usize) -> Vec<(CycleId, usize)> {
+    let mut positions = vec![(CycleId::Cycle1, 0); dimension];
+    for (i, &v) in solution.cycle1.iter().enumerate() {
+        positions[v] = (CycleId::Cycle1, i);
+    }
+    for (i, &v) in solution.cycle2.iter().enumerate() {
+        positions[v] = (CycleId::Cycle2, i);
+    }
+    positions
+}
+
+/// Edges removed and added by `move_type`, and the vertices it moves.
+fn move_attributes(
+    move_type: &Move,
+    solution: &Solution,
+    positions: &[(CycleId, usize)],
+) -> MoveAttributes {
+    let incident = |v: usize| -> [Edge; 2] {
+        let (cycle, pos) = positions[v];
+        let cycle = solution.get_cycle(cycle);
+        let n = cycle.len();
+        [(cycle[(pos + n - 1) % n], v), (v, cycle[(pos + 1) % n])]
+    };
+    match *move_type {
+        Move::InterRouteExchange { v1, v2 } | Move::IntraRouteVertexExchange { v1, v2, .. } => {
+            // Swapping two vertices relabels every edge incident to them
+            let swap = |x: usize| {
+                if x == v1 {
+                    v2
+                } else if x == v2 {
+                    v1
+                } else {
+                    x
+                }
+            };
+            let mut before: Vec<Edge> = incident(v1)
+                .into_iter()
+                .chain(incident(v2))
+                .map(|(x, y)| normalized(x, y))
+                .collect();
+            before.sort_unstable();
+            before.dedup();
+            let mut after: Vec<Edge> = before
+                .iter()
+                .map(|&(x, y)| normalized(swap(x), swap(y)))
+                .collect();
+            after.sort_unstable();
+            after.dedup();
+            MoveAttributes {
+                removed: before
+                    .iter()
+                    .copied()
+                    .filter(|e| !after.contains(e))
+                    .collect(),
+                added: after
+                    .iter()
+                    .copied()
+                    .filter(|e| !before.contains(e))
+                    .collect(),
+                moved: vec![v1, v2],
+            }
+        }
+        Move::IntraRouteEdgeExchange { a, b, c, d, .. } => MoveAttributes {
+            removed: vec![normalized(a, b), normalized(c, d)],
+            added: vec![normalized(a, c), normalized(b, d)],
+            moved: vec![a, b, c, d],
+        },
+        // Not generated by tabu search
+        Move::IntraRoute3Opt { .. } | Move::IntraRouteOrOpt { .. } => MoveAttributes {
+            removed: Vec::new(),
+            added: Vec::new(),
+            moved: Vec::new(),
+        },
+    }
+}
+
+impl BudgetedAlgorithm for TabuSearch {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+
+        let mut current_solution = generate_initial_solution(instance, self.initial_solution_type);
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress.record_evaluation(current_cost);
+        let mut tabu_list = TabuList::new(self.attribute, instance.dimension);
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            progress_callback(ProgressEvent::IterationStarted {
+                iteration: iterations,
+                best_cost,
+            });
+
+            let positions = node_positions(&current_solution, instance.dimension);
+            let moves = self.generate_moves(instance, &current_solution, &positions);
+            let Some((chosen, attributes)) = select_move(
+                moves,
+                &current_solution,
+                &positions,
+                &tabu_list,
+                iterations,
+                current_cost,
+                best_cost,
+            ) else {
+                progress_callback(ProgressEvent::Message(
+                    "No moves available, stopping".to_string(),
+                ));
+                break StopReason::Converged;
+            };
+
+            chosen.move_type.apply(&mut current_solution);
+            current_cost += chosen.delta;
+            tabu_list.record(
+                &attributes,
+                iterations,
+                rng.random_range(self.tenure.clone()),
+            );
+            progress.record_evaluation(current_cost);
+
+            if current_cost < best_cost {
+                best_cost = current_cost;
+                best_solution = current_solution.clone();
+                progress_callback(ProgressEvent::NewBest {
+                    iteration: iterations,
+                    cost: best_cost,
+                    elapsed: progress.elapsed(),
+                });
+            }
+            progress_callback(ProgressEvent::IterationFinished {
+                iteration: iterations,
+                current_cost,
+                best_cost,
+            });
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for TabuSearch {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -0,0 +1,238 @@
+//! Threshold accepting and record-to-record travel over random, delta-evaluated moves.
+//!
+//! Both accept any move that keeps the cost within a deterministic bound: threshold
+//! accepting bounds the worsening of a single move by a threshold that shrinks over time,
+//! record-to-record travel bounds the new cost relative to the best cost found so far.
+
+use crate::algorithm::{
+    BudgetedAlgorithm, ProgressCallback, ProgressEvent, SolveOutcome, TspAlgorithm,
+    reports_move_iteration,
+};
+use crate::algorithms::local_search::base::{
+    InitialSolutionType, NeighborhoodType, generate_initial_solution,
+};
+use crate::moves::random::random_evaluated_move;
+use crate::termination::{SearchProgress, Termination};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::{Rng, rng};
+use std::time::Duration;
+
+/// Number of random moves sampled to calibrate the initial threshold.
+const CALIBRATION_SAMPLES: usize = 500;
+
+/// Which moves are accepted besides improving ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdRule {
+    /// Accept a move if its delta does not exceed the threshold. The threshold starts at
+    /// `initial_threshold` (or the mean worsening delta of random moves if `None`) and is
+    /// multiplied by `decay` every `epoch_length` iterations.
+    ThresholdAccepting {
+        initial_threshold: Option<f64>,
+        decay: f64,
+        epoch_length: usize,
+    },
+    /// Accept a move if the new cost is at most `(1 + deviation) * best_cost`.
+    RecordToRecord { deviation: f64 },
+}
+
+pub struct ThresholdAcceptingSearch {
+    /// Intra-route moves: `VertexExchange` or `EdgeExchange`.
+    neighborhood: NeighborhoodType,
+    rule: ThresholdRule,
+    initial_solution_type: InitialSolutionType,
+    name_str: String,
+}
+
+impl ThresholdAcceptingSearch {
+    pub fn new(
+        neighborhood: NeighborhoodType,
+        rule: ThresholdRule,
+        initial_solution_type: InitialSolutionType,
+    ) -> Self {
+        assert!(
+            matches!(
+                neighborhood,
+                NeighborhoodType::VertexExchange | NeighborhoodType::EdgeExchange
+            ),
+            "Threshold accepting supports only vertex and edge exchange neighborhoods"
+        );
+        let name_str = match rule {
+            ThresholdRule::ThresholdAccepting {
+                initial_threshold,
+                decay,
+                epoch_length,
+            } => {
+                let t0 = match initial_threshold {
+                    Some(t) => format!("{:.1}", t),
+                    None => "auto".to_string(),
+                };
+                format!(
+                    "Threshold Accepting ({:?}, T0={}, decay={}, epoch={}, init={:?})",
+                    neighborhood, t0, decay, epoch_length, initial_solution_type
+                )
+            }
+            ThresholdRule::RecordToRecord { deviation } => format!(
+                "Record-to-Record Travel ({:?}, deviation={}, init={:?})",
+                neighborhood, deviation, initial_solution_type
+            ),
+        };
+        Self {
+            neighborhood,
+            rule,
+            initial_solution_type,
+            name_str,
+        }
+    }
+}
+
+/// Mean delta of the worsening moves among random moves of `solution`. The sampled moves
+/// count as evaluations of `progress`.
+fn mean_worsening_delta<R: Rng + ?Sized>(
+    solution: &Solution,
+    instance: &TsplibInstance,
+    neighborhood: NeighborhoodType,
+    progress: &mut SearchProgress,
+    rng: &mut R,
+) -> f64 {
+    let worsening: Vec<i32> = (0..CALIBRATION_SAMPLES)
+        .filter_map(|_| random_evaluated_move(solution, instance, neighborhood, rng))
+        .inspect(|_| progress.evaluations += 1)
+        .map(|m| m.delta)
+        .filter(|&delta| delta > 0)
+        .collect();
+    if worsening.is_empty() {
+        return 1.0;
+    }
+    worsening.iter().map(|&d| d as f64).sum::<f64>() / worsening.len() as f64
+}
+
+impl BudgetedAlgorithm for ThresholdAcceptingSearch {
+    fn solve_budgeted(
+        &self,
+        instance: &TsplibInstance,
+        termination: &Termination,
+        progress_callback: ProgressCallback,
+    ) -> SolveOutcome {
+        let mut rng = rng();
+        let mut progress = SearchProgress::new();
+
+        let mut current_solution = generate_initial_solution(instance, self.initial_solution_type);
+        let mut current_cost = current_solution.calculate_cost(instance);
+        let mut best_solution = current_solution.clone();
+        let mut best_cost = current_cost;
+        progress.record_evaluation(current_cost);
+
+        let mut threshold = match self.rule {
+            ThresholdRule::ThresholdAccepting {
+                initial_threshold, ..
+            } => initial_threshold.unwrap_or_else(|| {
+                mean_worsening_delta(
+                    &current_solution,
+                    instance,
+                    self.neighborhood,
+                    &mut progress,
+                    &mut rng,
+                )
+            }),
+            ThresholdRule::RecordToRecord { .. } => 0.0,
+        };
+        if let ThresholdRule::ThresholdAccepting { .. } = self.rule {
+            progress_callback(ProgressEvent::Message(format!(
+                "[{}] Initial threshold: {:.2}",
+                self.name_str, threshold
+            )));
+        }
+
+        let stop_reason = loop {
+            if let Some(reason) = termination.check(&progress) {
+                break reason;
+            }
+            progress.iterations += 1;
+            let iterations = progress.iterations;
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationStarted {
+                    iteration: iterations,
+                    best_cost,
+                });
+            }
+
+            if let Some(candidate) =
+                random_evaluated_move(&current_solution, instance, self.neighborhood, &mut rng)
+            {
+                // Rejected moves are worsening, so counting them cannot move the best cost
+                progress.record_evaluation(current_cost + candidate.delta);
+                let accept = candidate.delta <= 0
+                    || match self.rule {
+                        ThresholdRule::ThresholdAccepting { .. } => {
+                            candidate.delta as f64 <= threshold
+                        }
+                        ThresholdRule::RecordToRecord { deviation } => {
+                            (current_cost + candidate.delta) as f64
+                                <= (1.0 + deviation) * best_cost as f64
+                        }
+                    };
+                if accept {
+                    candidate.move_type.apply(&mut current_solution);
+                    current_cost += candidate.delta;
+                    if current_cost < best_cost {
+                        best_cost = current_cost;
+                        best_solution = current_solution.clone();
+                        progress_callback(ProgressEvent::NewBest {
+                            iteration: iterations,
+                            cost: best_cost,
+                            elapsed: progress.elapsed(),
+                        });
+                    }
+                }
+            }
+            if let ThresholdRule::ThresholdAccepting {
+                decay,
+                epoch_length,
+                ..
+            } = self.rule
+                && iterations.is_multiple_of(epoch_length.max(1))
+            {
+                threshold *= decay;
+            }
+
+            if reports_move_iteration(iterations) {
+                progress_callback(ProgressEvent::IterationFinished {
+                    iteration: iterations,
+                    current_cost,
+                    best_cost,
+                });
+            }
+        };
+
+        progress_callback(ProgressEvent::Finished {
+            iterations: progress.iterations,
+            best_cost,
+            stop_reason,
+        });
+        SolveOutcome {
+            solution: best_solution,
+            iterations: progress.iterations,
+            stop_reason,
+        }
+    }
+}
+
+impl TspAlgorithm for ThresholdAcceptingSearch {
+    fn name(&self) -> &str {
+        &self.name_str
+    }
+
+    fn solve_with_feedback(
+        &self,
+        instance: &TsplibInstance,
+        progress_callback: ProgressCallback,
+    ) -> Solution {
+        let time_limit = Duration::from_secs(60);
+        self.solve_budgeted(
+            instance,
+            &Termination::TimeLimit(time_limit),
+            progress_callback,
+        )
+        .solution
+    }
+}
@@ -1,5 +1,6 @@
-use crate::algorithm::{ProgressCallback, TspAlgorithm};
+use crate::algorithm::{BudgetedAlgorithm, ProgressCallback, ProgressEvent};
 use crate::algorithms::local_search::base::LocalSearch;
+use crate::termination::{CancellationToken, StopReason, Termination};
 use crate::tsplib::{Solution, TsplibInstance};
 use crate::utils::generate_random_solution;
 use plotters::prelude::*;
@@ -63,7 +64,6 @@ pub fn calculate_similarity(sol1: &Solution, sol2: &Solution, instance: &TsplibI
 /// Count vertex pairs that are in the same cycle in both solutions
 fn count_vertex_pairs_same_cycle(sol1: &Solution, sol2: &Solution, instance: &TsplibInstance) -> usize {
     let n = instance.size();
-    let mut count = 0;
     
     // Create mappings: vertex -> cycle_id for both solutions
     let mut sol1_cycle_map = vec![0; n]; // 0 for cycle1, 1 for cycle2
@@ -85,19 +85,13 @@ fn count_vertex_pairs_same_cycle(sol1: &Solution, sol2: &Solution, instance: &Ts
         sol2_cycle_map[vertex] = 1;
     }
     
-    // Count pairs that are in the same cycle in both solutions
-    for i in 0..n {
-        for j in i + 1..n {
-            let same_cycle_sol1 = sol1_cycle_map[i] == sol1_cycle_map[j];
-            let same_cycle_sol2 = sol2_cycle_map[i] == sol2_cycle_map[j];
-            
-            if same_cycle_sol1 && same_cycle_sol2 {
-                count += 1;
-            }
-        }
+    // Pairs that share a cycle in both solutions are exactly the pairs with the same
+    // (sol1 cycle, sol2 cycle) combination, so counting the combinations is enough
+    let mut combination_sizes = [0usize; 4];
+    for v in 0..n {
+        combination_sizes[sol1_cycle_map[v] * 2 + sol2_cycle_map[v]] += 1;
     }
-    
-    count
+    combination_sizes.iter().map(|&k| k * k.saturating_sub(1) / 2).sum()
 }
 
 /// Count common edges between two solutions
@@ -132,18 +126,21 @@ fn count_common_edges(sol1: &Solution, sol2: &Solution) -> usize {
     count
 }
 
-/// Generate 1000 random local optima using the given local search algorithm
+/// Generate 1000 random local optima using the given local search algorithm.
+/// Stops early when `cancel` fires, returning the optima completed so far.
 pub fn generate_random_local_optima(
     instance: &TsplibInstance,
     local_search: &LocalSearch,
     num_optima: usize,
+    cancel: &CancellationToken,
     progress_callback: ProgressCallback,
 ) -> Vec<Solution> {
     let mut local_optima = Vec::with_capacity(num_optima);
+    let termination = Termination::Cancelled(cancel.clone());
     
     for i in 0..num_optima {
         if i % 100 == 0 {
-            progress_callback(format!("Generating local optimum {}/{}", i + 1, num_optima));
+            progress_callback(ProgressEvent::Message(format!("Generating local optimum {}/{}", i + 1, num_optima)));
         }
         
         // Generate random starting solution
@@ -151,13 +148,17 @@ pub fn generate_random_local_optima(
         
         // Apply local search to get local optimum
         // Note: LocalSearch with InitialSolutionType::Random will generate its own random solution
-        let mut dummy_callback = |_: String| {};
-        let local_optimum = local_search.solve_with_feedback(instance, &mut dummy_callback);
+        let mut dummy_callback = |_: ProgressEvent| {};
+        let outcome = local_search.solve_budgeted(instance, &termination, &mut dummy_callback);
+        if outcome.stop_reason == StopReason::Cancelled {
+            // An interrupted descent is not a local optimum
+            break;
+        }
         
-        local_optima.push(local_optimum);
+        local_optima.push(outcome.solution);
     }
     
-    progress_callback(format!("Generated {} local optima", num_optima));
+    progress_callback(ProgressEvent::Message(format!("Generated {} local optima", local_optima.len())));
     local_optima
 }
 
@@ -209,41 +210,55 @@ fn calculate_correlation(x_values: &[f64], y_values: &[f64]) -> f64 {
     }
 }
 
-/// Perform global convexity analysis
-pub fn analyze_global_convexity<T: TspAlgorithm>(
+/// Perform global convexity analysis.
+/// If `cancel` fires, the best algorithm returns its incumbent and the analysis
+/// runs on the local optima generated up to that point.
+pub fn analyze_global_convexity<T: BudgetedAlgorithm>(
     instance: &TsplibInstance,
     instance_name: &str,
     best_algorithm: &T,
+    best_algorithm_termination: &Termination,
     local_search: &LocalSearch,
+    cancel: &CancellationToken,
     progress_callback: ProgressCallback,
 ) -> ConvexityAnalysisResult {
-    progress_callback("Starting global convexity analysis...".to_string());
+    progress_callback(ProgressEvent::Message("Starting global convexity analysis...".to_string()));
     
     // 1. Generate very good solution using best algorithm
-    progress_callback("Generating best solution...".to_string());
-    let mut best_callback = |s: String| {
-        progress_callback(format!("Best algorithm: {}", s));
+    progress_callback(ProgressEvent::Message("Generating best solution...".to_string()));
+    let mut best_callback = |event: ProgressEvent| {
+        progress_callback(event.nested("Best algorithm"));
     };
-    let best_solution = best_algorithm.solve_with_feedback(instance, &mut best_callback);
+    let termination = best_algorithm_termination
+        .clone()
+        .or(Termination::Cancelled(cancel.clone()));
+    let best_solution = best_algorithm
+        .solve_budgeted(instance, &termination, &mut best_callback)
+        .solution;
     let best_cost = best_solution.calculate_cost(instance);
-    progress_callback(format!("Best solution cost: {}", best_cost));
+    progress_callback(ProgressEvent::Message(format!("Best solution cost: {}", best_cost)));
     
     // 2. Generate 1000 random local optima
-    progress_callback("Generating 1000 random local optima...".to_string());
+    progress_callback(ProgressEvent::Message("Generating 1000 random local optima...".to_string()));
     let local_optima = generate_random_local_optima(
         instance,
         local_search,
         1000,
-        &mut |s| progress_callback(format!("Local optima: {}", s)),
+        cancel,
+        &mut |event| progress_callback(event.nested("Local optima")),
     );
     
     // 3. Calculate similarities and create data points
-    progress_callback("Calculating similarities...".to_string());
+    progress_callback(ProgressEvent::Message("Calculating similarities...".to_string()));
     let mut data_points = Vec::with_capacity(local_optima.len());
     
     for (i, local_optimum) in local_optima.iter().enumerate() {
+        if cancel.is_cancelled() {
+            progress_callback(ProgressEvent::Message(format!("Cancelled after processing {} optima", i)));
+            break;
+        }
         if i % 100 == 0 {
-            progress_callback(format!("Processing optimum {}/{}", i + 1, local_optima.len()));
+            progress_callback(ProgressEvent::Message(format!("Processing optimum {}/{}", i + 1, local_optima.len())));
         }
         
         let cost = local_optimum.calculate_cost(instance);
@@ -271,7 +286,7 @@ pub fn analyze_global_convexity<T: TspAlgorithm>(
     }
     
     // 4. Calculate correlations
-    progress_callback("Calculating correlations...".to_string());
+    progress_callback(ProgressEvent::Message("Calculating correlations...".to_string()));
     let costs: Vec<f64> = data_points.iter().map(|dp| dp.cost as f64).collect();
     
     // Correlations for similarity to S_best
@@ -298,10 +313,10 @@ pub fn analyze_global_convexity<T: TspAlgorithm>(
     let correlation_avg_vertex_pairs = calculate_correlation(&costs, &avg_vertex_pairs_similarities);
     let correlation_avg_common_edges = calculate_correlation(&costs, &avg_common_edges_similarities);
     
-    progress_callback(format!(
+    progress_callback(ProgressEvent::Message(format!(
         "Correlations - S_best VP: {:.4}, S_best CE: {:.4}, Avg VP: {:.4}, Avg CE: {:.4}",
         correlation_vertex_pairs, correlation_common_edges, correlation_avg_vertex_pairs, correlation_avg_common_edges
-    ));
+    )));
     
     ConvexityAnalysisResult {
         instance_name: instance_name.to_string(),
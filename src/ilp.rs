@@ -0,0 +1,568 @@
+//! ILP export of the two-cycle problem and import of MIP solver solutions.
+//!
+//! Formulation, with cycle `k` in {1, 2} and nodes `i < j`:
+//!
+//! * `x{k}_{i}_{j}` binary: edge `{i, j}` is used by cycle `k`,
+//! * `y{k}_{i}` binary: node `i` belongs to cycle `k`,
+//! * minimize `sum d(i, j) * x{k}_{i}_{j}`,
+//! * `assign_{i}`: `y1_{i} + y2_{i} = 1`,
+//! * `degree{k}_{i}`: `sum_j x{k}_{i}_{j} - 2 y{k}_{i} = 0`,
+//! * `balance{k}`: `sum_i y{k}_{i}` equals `ceil(n / 2)` for cycle 1 and `floor(n / 2)` for cycle 2,
+//! * lazy `subtour{k}_{c}`: `sum_{i, j in S} x{k}_{i}_{j} <= |S| - 1` for the node sets `S` given
+//!   as cuts (only where `|S|` is below the size of cycle `k`).
+//!
+//! Subtour elimination has exponentially many constraints, so only the given cuts are
+//! written. When a solution read back still contains subtours, `read_solution` returns them
+//! as `IlpError::Subtours`; adding them to the cuts and exporting again gives an offline
+//! cutting-plane loop.
+
+use crate::tsplib::{Solution, TsplibInstance};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use thiserror::Error;
+
+/// Maximal number of terms written on one line of an LP file.
+const LP_TERMS_PER_LINE: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum IlpError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Unsupported instance: {0}")]
+    Unsupported(String),
+    #[error("Invalid solution: {0}")]
+    InvalidSolution(String),
+    #[error("Solution contains {} subtours", .0.len())]
+    Subtours(Vec<Vec<usize>>),
+}
+
+struct Constraint {
+    name: String,
+    terms: Vec<(usize, i64)>,
+    /// `'E'`, `'L'` or `'G'`, as in the MPS `ROWS` section.
+    sense: char,
+    rhs: i64,
+}
+
+struct IlpModel {
+    name: String,
+    variables: Vec<String>,
+    objective: Vec<(usize, i64)>,
+    constraints: Vec<Constraint>,
+    lazy_constraints: Vec<Constraint>,
+}
+
+fn edge_variable(cycle: usize, i: usize, j: usize) -> String {
+    let (i, j) = if i < j { (i, j) } else { (j, i) };
+    format!("x{}_{}_{}", cycle, i, j)
+}
+
+fn build_model(
+    instance: &TsplibInstance,
+    subtour_cuts: &[Vec<usize>],
+) -> Result<IlpModel, IlpError> {
+    let n = instance.size();
+    // A two-node cycle uses its only edge twice, which binary edge variables cannot express
+    if n < 6 {
+        return Err(IlpError::Unsupported(format!(
+            "the formulation needs at least 6 nodes, got {}",
+            n
+        )));
+    }
+    let sizes = [n.div_ceil(2), n / 2];
+
+    let mut variables = Vec::new();
+    let mut objective = Vec::new();
+    // edge_index[k][i * n + j] for i < j, node_index[k][i]
+    let mut edge_index = vec![vec![usize::MAX; n * n]; 2];
+    let mut node_index = vec![vec![0; n]; 2];
+    for k in 0..2 {
+        for i in 0..n {
+            for j in i + 1..n {
+                edge_index[k][i * n + j] = variables.len();
+                objective.push((variables.len(), instance.distance(i, j) as i64));
+                variables.push(edge_variable(k + 1, i, j));
+            }
+        }
+        for (i, index) in node_index[k].iter_mut().enumerate() {
+            *index = variables.len();
+            variables.push(format!("y{}_{}", k + 1, i));
+        }
+    }
+    let edge = |k: usize, i: usize, j: usize| edge_index[k][i.min(j) * n + i.max(j)];
+
+    let mut constraints = Vec::new();
+    for (i, (&y1, &y2)) in node_index[0].iter().zip(&node_index[1]).enumerate() {
+        constraints.push(Constraint {
+            name: format!("assign_{}", i),
+            terms: vec![(y1, 1), (y2, 1)],
+            sense: 'E',
+            rhs: 1,
+        });
+    }
+    for (k, nodes) in node_index.iter().enumerate() {
+        for (i, &y) in nodes.iter().enumerate() {
+            let mut terms: Vec<(usize, i64)> = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (edge(k, i, j), 1))
+                .collect();
+            terms.push((y, -2));
+            constraints.push(Constraint {
+                name: format!("degree{}_{}", k + 1, i),
+                terms,
+                sense: 'E',
+                rhs: 0,
+            });
+        }
+        constraints.push(Constraint {
+            name: format!("balance{}", k + 1),
+            terms: nodes.iter().map(|&y| (y, 1)).collect(),
+            sense: 'E',
+            rhs: sizes[k] as i64,
+        });
+    }
+
+    let mut lazy_constraints = Vec::new();
+    for (c, set) in subtour_cuts.iter().enumerate() {
+        if let Some(&node) = set.iter().find(|&&v| v >= n) {
+            return Err(IlpError::Unsupported(format!(
+                "subtour cut {} contains node {} outside the instance",
+                c, node
+            )));
+        }
+        for (k, &size) in sizes.iter().enumerate() {
+            if set.len() < 3 || set.len() >= size {
+                continue;
+            }
+            let terms = set
+                .iter()
+                .enumerate()
+                .flat_map(|(a, &i)| set[a + 1..].iter().map(move |&j| (i, j)))
+                .map(|(i, j)| (edge(k, i, j), 1))
+                .collect();
+            lazy_constraints.push(Constraint {
+                name: format!("subtour{}_{}", k + 1, c),
+                terms,
+                sense: 'L',
+                rhs: set.len() as i64 - 1,
+            });
+        }
+    }
+
+    Ok(IlpModel {
+        name: instance.name.clone(),
+        variables,
+        objective,
+        constraints,
+        lazy_constraints,
+    })
+}
+
+/// Node sets for the initial subtour cut pool: every node together with its `k` nearest
+/// neighbours, and all shorter prefixes of that list of at least three nodes.
+pub fn default_subtour_cuts(instance: &TsplibInstance, k: usize) -> Vec<Vec<usize>> {
+    let n = instance.size();
+    let mut cuts = Vec::new();
+    for u in 0..n {
+        let mut others: Vec<usize> = (0..n).filter(|&v| v != u).collect();
+        others.sort_by_key(|&v| instance.distance(u, v));
+        for size in 2..=k.min(n - 1) {
+            let mut set: Vec<usize> = std::iter::once(u)
+                .chain(others[..size].iter().copied())
+                .collect();
+            set.sort_unstable();
+            cuts.push(set);
+        }
+    }
+    cuts.sort();
+    cuts.dedup();
+    cuts
+}
+
+fn write_lp_terms(
+    writer: &mut impl Write,
+    model: &IlpModel,
+    terms: &[(usize, i64)],
+) -> io::Result<()> {
+    for (index, &(variable, coefficient)) in terms.iter().enumerate() {
+        if index > 0 && index % LP_TERMS_PER_LINE == 0 {
+            write!(writer, "\n   ")?;
+        }
+        let sign = if coefficient < 0 { '-' } else { '+' };
+        if index == 0 && coefficient >= 0 {
+            write!(writer, " ")?;
+        } else {
+            write!(writer, " {} ", sign)?;
+        }
+        if coefficient.abs() != 1 {
+            write!(writer, "{} ", coefficient.abs())?;
+        }
+        write!(writer, "{}", model.variables[variable])?;
+    }
+    Ok(())
+}
+
+fn write_lp_constraint(
+    writer: &mut impl Write,
+    model: &IlpModel,
+    constraint: &Constraint,
+) -> io::Result<()> {
+    write!(writer, " {}:", constraint.name)?;
+    write_lp_terms(writer, model, &constraint.terms)?;
+    let sense = match constraint.sense {
+        'L' => "<=",
+        'G' => ">=",
+        _ => "=",
+    };
+    writeln!(writer, " {} {}", sense, constraint.rhs)
+}
+
+/// Writes the model in CPLEX LP format; the subtour cuts go to the `Lazy Constraints` section.
+pub fn write_lp(
+    instance: &TsplibInstance,
+    subtour_cuts: &[Vec<usize>],
+    path: &Path,
+) -> Result<(), IlpError> {
+    let model = build_model(instance, subtour_cuts)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(
+        writer,
+        "\\ Two balanced cycles for {} ({} nodes)",
+        model.name,
+        instance.size()
+    )?;
+    writeln!(writer, "Minimize")?;
+    write!(writer, " obj:")?;
+    write_lp_terms(&mut writer, &model, &model.objective)?;
+    writeln!(writer)?;
+    writeln!(writer, "Subject To")?;
+    for constraint in &model.constraints {
+        write_lp_constraint(&mut writer, &model, constraint)?;
+    }
+    if !model.lazy_constraints.is_empty() {
+        writeln!(writer, "Lazy Constraints")?;
+        for constraint in &model.lazy_constraints {
+            write_lp_constraint(&mut writer, &model, constraint)?;
+        }
+    }
+    writeln!(writer, "Binary")?;
+    for chunk in model.variables.chunks(LP_TERMS_PER_LINE) {
+        writeln!(writer, " {}", chunk.join(" "))?;
+    }
+    writeln!(writer, "End")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the model in free MPS format; the subtour cuts go to the `LAZYCONS` section
+/// (a CPLEX extension that other solvers may reject, so pass no cuts for them).
+pub fn write_mps(
+    instance: &TsplibInstance,
+    subtour_cuts: &[Vec<usize>],
+    path: &Path,
+) -> Result<(), IlpError> {
+    let model = build_model(instance, subtour_cuts)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    // Column-wise view: (row name, coefficient) of every variable, objective first
+    let mut columns: Vec<Vec<(&str, i64)>> = vec![Vec::new(); model.variables.len()];
+    for &(variable, coefficient) in &model.objective {
+        columns[variable].push(("obj", coefficient));
+    }
+    for constraint in model.constraints.iter().chain(&model.lazy_constraints) {
+        for &(variable, coefficient) in &constraint.terms {
+            columns[variable].push((&constraint.name, coefficient));
+        }
+    }
+
+    writeln!(writer, "NAME {}", model.name)?;
+    writeln!(writer, "ROWS")?;
+    writeln!(writer, " N obj")?;
+    for constraint in &model.constraints {
+        writeln!(writer, " {} {}", constraint.sense, constraint.name)?;
+    }
+    if !model.lazy_constraints.is_empty() {
+        writeln!(writer, "LAZYCONS")?;
+        for constraint in &model.lazy_constraints {
+            writeln!(writer, " {} {}", constraint.sense, constraint.name)?;
+        }
+    }
+    writeln!(writer, "COLUMNS")?;
+    for (variable, entries) in model.variables.iter().zip(&columns) {
+        for (row, coefficient) in entries {
+            writeln!(writer, " {} {} {}", variable, row, coefficient)?;
+        }
+    }
+    writeln!(writer, "RHS")?;
+    for constraint in model.constraints.iter().chain(&model.lazy_constraints) {
+        if constraint.rhs != 0 {
+            writeln!(writer, " rhs {} {}", constraint.name, constraint.rhs)?;
+        }
+    }
+    writeln!(writer, "BOUNDS")?;
+    for variable in &model.variables {
+        writeln!(writer, " BV bnd {}", variable)?;
+    }
+    writeln!(writer, "ENDATA")?;
+    writer.flush()?;
+    Ok(())
+}
+
+lazy_static! {
+    static ref EDGE_VARIABLE: Regex = Regex::new(r"^x([12])_(\d+)_(\d+)$").unwrap();
+    static ref XML_VARIABLE: Regex =
+        Regex::new(r#"<variable\b[^>]*?\bname="([^"]+)"[^>]*?\bvalue="([^"]+)""#).unwrap();
+}
+
+/// Edge variables set to 1 in a solution file: `(cycle index 0/1, i, j)`.
+///
+/// Understands CPLEX XML solutions (`<variable name=".." value=".."/>`) and the plain-text
+/// formats of Gurobi, SCIP, CBC and HiGHS, where each line holds a variable name followed
+/// by its value.
+fn parse_solution_edges(content: &str) -> Vec<(usize, usize, usize)> {
+    let mut assignments: Vec<(String, f64)> = Vec::new();
+    if content.contains("<variable") {
+        for captures in XML_VARIABLE.captures_iter(content) {
+            if let Ok(value) = captures[2].parse() {
+                assignments.push((captures[1].to_string(), value));
+            }
+        }
+    } else {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if let Some(position) = tokens.iter().position(|t| EDGE_VARIABLE.is_match(t))
+                && let Some(value) = tokens.get(position + 1).and_then(|t| t.parse().ok())
+            {
+                assignments.push((tokens[position].to_string(), value));
+            }
+        }
+    }
+
+    assignments
+        .into_iter()
+        .filter(|(_, value)| *value > 0.5)
+        .filter_map(|(name, _)| {
+            let captures = EDGE_VARIABLE.captures(&name)?;
+            let cycle = captures[1].parse::<usize>().ok()? - 1;
+            Some((cycle, captures[2].parse().ok()?, captures[3].parse().ok()?))
+        })
+        .collect()
+}
+
+/// Reads a MIP solver's solution of the model written by `write_lp` / `write_mps`.
+///
+/// Returns `IlpError::Subtours` with the node sets of all subtours when a cycle falls apart
+/// into several ones (the cut pool was too small); they can be passed as `subtour_cuts`.
+pub fn read_solution(instance: &TsplibInstance, path: &Path) -> Result<Solution, IlpError> {
+    solution_from_str(instance, &fs::read_to_string(path)?)
+}
+
+/// Reconstructs both cycles from the contents of a solution file.
+fn solution_from_str(instance: &TsplibInstance, content: &str) -> Result<Solution, IlpError> {
+    let n = instance.size();
+    let mut adjacency: [Vec<Vec<usize>>; 2] = [vec![Vec::new(); n], vec![Vec::new(); n]];
+    for (cycle, i, j) in parse_solution_edges(content) {
+        if i >= n || j >= n || i == j {
+            return Err(IlpError::InvalidSolution(format!(
+                "edge ({}, {}) is not an edge of the instance",
+                i, j
+            )));
+        }
+        adjacency[cycle][i].push(j);
+        adjacency[cycle][j].push(i);
+    }
+
+    // Every node must be checked before walking, as a walk passes through unchecked nodes
+    let mut cycle_of = vec![0; n];
+    for (v, k) in cycle_of.iter_mut().enumerate() {
+        let degrees = [adjacency[0][v].len(), adjacency[1][v].len()];
+        *k = match degrees {
+            [2, 0] => 0,
+            [0, 2] => 1,
+            _ => {
+                return Err(IlpError::InvalidSolution(format!(
+                    "node {} has degrees {:?} in the two cycles",
+                    v, degrees
+                )));
+            }
+        };
+    }
+
+    let mut cycles: [Vec<Vec<usize>>; 2] = [Vec::new(), Vec::new()];
+    let mut seen = vec![false; n];
+    for v in 0..n {
+        if seen[v] {
+            continue;
+        }
+        // Walk the component of `v`; all its nodes have degree 2 in cycle k
+        let k = cycle_of[v];
+        let mut component = vec![v];
+        seen[v] = true;
+        let (mut previous, mut current) = (v, adjacency[k][v][0]);
+        while current != v {
+            component.push(current);
+            seen[current] = true;
+            let next = adjacency[k][current]
+                .iter()
+                .copied()
+                .find(|&w| w != previous)
+                .unwrap_or(previous);
+            previous = current;
+            current = next;
+        }
+        cycles[k].push(component);
+    }
+
+    if cycles.iter().any(|components| components.len() > 1) {
+        return Err(IlpError::Subtours(
+            cycles
+                .into_iter()
+                .filter(|components| components.len() > 1)
+                .flatten()
+                .collect(),
+        ));
+    }
+    let [mut cycle1, mut cycle2] = cycles;
+    let solution = Solution::new(
+        cycle1.pop().unwrap_or_default(),
+        cycle2.pop().unwrap_or_default(),
+    );
+    // Every node lies on one of the cycles, so only the sizes remain to be checked
+    if solution.cycle1.len() != n.div_ceil(2) {
+        return Err(IlpError::InvalidSolution(format!(
+            "cycle sizes {} and {} are not balanced",
+            solution.cycle1.len(),
+            solution.cycle2.len()
+        )));
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `n` nodes on two circles far apart, the first `ceil(n / 2)` on the first one.
+    fn two_cluster_instance(n: usize) -> TsplibInstance {
+        let coordinates = (0..n)
+            .map(|v| {
+                let angle = v as f64 * std::f64::consts::TAU / n as f64;
+                let offset = if v < n.div_ceil(2) { 0.0 } else { 1000.0 };
+                (offset + 100.0 * angle.cos(), 100.0 * angle.sin())
+            })
+            .collect();
+        TsplibInstance::from_coordinates("clusters", coordinates)
+    }
+
+    /// Edge variable names of a cycle through `nodes` in cycle `k` (1 or 2).
+    fn cycle_variables(k: usize, nodes: &[usize]) -> Vec<String> {
+        (0..nodes.len())
+            .map(|i| {
+                let (u, v) = (nodes[i], nodes[(i + 1) % nodes.len()]);
+                edge_variable(k, u.min(v), u.max(v))
+            })
+            .collect()
+    }
+
+    fn assert_cycles(solution: &Solution, instance: &TsplibInstance, first: &[usize]) {
+        assert!(solution.is_valid(instance));
+        let mut cycle1 = solution.cycle1.clone();
+        cycle1.sort_unstable();
+        assert_eq!(cycle1, first);
+    }
+
+    #[test]
+    fn reads_cplex_xml_solution() {
+        let instance = two_cluster_instance(7);
+        let mut content = String::from(
+            "<?xml version = \"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<CPLEXSolution version=\"1.2\">\n <variables>\n",
+        );
+        let variables = cycle_variables(1, &[0, 2, 1, 3])
+            .into_iter()
+            .chain(cycle_variables(2, &[4, 5, 6]));
+        for (index, name) in variables.enumerate() {
+            content.push_str(&format!(
+                "  <variable name=\"{}\" index=\"{}\" value=\"1\"/>\n",
+                name, index
+            ));
+        }
+        // Variables at zero and node variables are ignored
+        content.push_str("  <variable name=\"x1_0_4\" index=\"90\" value=\"0\"/>\n");
+        content.push_str("  <variable name=\"y1_0\" index=\"91\" value=\"1\"/>\n");
+        content.push_str(" </variables>\n</CPLEXSolution>\n");
+
+        let solution = solution_from_str(&instance, &content).unwrap();
+        assert_cycles(&solution, &instance, &[0, 1, 2, 3]);
+        assert_eq!(solution.cycle2.len(), 3);
+    }
+
+    #[test]
+    fn reads_gurobi_and_cbc_text_solutions() {
+        let instance = two_cluster_instance(8);
+        let variables: Vec<String> = cycle_variables(1, &[0, 1, 2, 3])
+            .into_iter()
+            .chain(cycle_variables(2, &[4, 6, 5, 7]))
+            .collect();
+
+        let mut gurobi = String::from("# Solution for model two_cycles\n# Objective value = 1234\n");
+        let mut cbc = String::from("Optimal - objective value 1234.00000000\n");
+        for (index, name) in variables.iter().enumerate() {
+            gurobi.push_str(&format!("{} 1\n", name));
+            cbc.push_str(&format!("{:>7} {:<20} {:>14} {:>14}\n", index, name, 1, 0));
+        }
+        gurobi.push_str("x1_0_5 0\ny1_0 1\n");
+
+        for content in [gurobi, cbc] {
+            let solution = solution_from_str(&instance, &content).unwrap();
+            assert_cycles(&solution, &instance, &[0, 1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn reports_subtours() {
+        let instance = two_cluster_instance(12);
+        let content: String = cycle_variables(1, &[0, 1, 2])
+            .into_iter()
+            .chain(cycle_variables(1, &[3, 4, 5]))
+            .chain(cycle_variables(2, &[6, 7, 8, 9, 10, 11]))
+            .map(|name| format!("{} 1\n", name))
+            .collect();
+
+        match solution_from_str(&instance, &content) {
+            Err(IlpError::Subtours(subtours)) => {
+                let mut subtours: Vec<Vec<usize>> = subtours
+                    .into_iter()
+                    .map(|mut nodes| {
+                        nodes.sort_unstable();
+                        nodes
+                    })
+                    .collect();
+                subtours.sort();
+                assert_eq!(subtours, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+            }
+            other => panic!("expected subtours, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_node_of_degree_three_without_walking_forever() {
+        let instance = two_cluster_instance(6);
+        // A walk from node 0 enters the triangle 2-3-4 through node 2 (degree 3) and,
+        // coming back to node 2 from node 4, would take node 3 again instead of node 0
+        let content: String = ["x1_2_3", "x1_2_4", "x1_3_4", "x1_0_2", "x1_0_1"]
+            .iter()
+            .map(|name| format!("{} 1\n", name))
+            .collect();
+        assert!(matches!(
+            solution_from_str(&instance, &content),
+            Err(IlpError::InvalidSolution(_))
+        ));
+    }
+}
@@ -7,20 +7,31 @@ use std::time::{Duration, Instant};
 mod algorithm;
 mod algorithms;
 mod global_convexity;
+mod ilp;
+mod lower_bound;
 mod moves;
+mod termination;
+mod trace;
 mod tsplib;
 mod utils;
 mod visualization;
 
-use algorithm::TspAlgorithm;
+use algorithm::{BudgetedAlgorithm, TspAlgorithm};
 use algorithms::enhanced_hae_or_opt::EnhancedHaeOrOpt;
+use algorithms::evolutionary::replacement::{EliteAwareReplacement, ReplaceFromWorst};
+use algorithms::evolutionary::selection::{TournamentSelection, UniformSelection};
 use algorithms::hae::Hae;
+use algorithms::recombination::RecombinationType;
+use algorithms::recombination::destroy_repair::DestroyRepairRecombination;
 use algorithms::local_search::base::{
     HeuristicAlgorithm, InitialSolutionType, LocalSearch, NeighborhoodType, SearchVariant,
 };
 use algorithms::msls::Msls;
+use termination::{CancellationToken, StopReason, Termination};
 use tsplib::{TsplibInstance, Solution};
-use crate::visualization::plot_solution;
+use crate::lower_bound::{best_lower_bound, gap_percent};
+use crate::trace::{ConvergenceTrace, TraceRecorder, write_traces_csv};
+use crate::visualization::{plot_convergence, plot_solution};
 
 const NUM_RUNS: usize = 20; // Number of runs for each algorithm per instance
 
@@ -72,6 +83,9 @@ impl AlgoRunStats {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== ZADANIE 7: WŁASNA METODA (UŚREDNIONE WYNIKI Z {} URUCHOMIEŃ) ===", NUM_RUNS);
+
+    // Ctrl+C stops the current run; the summary covers the runs finished so far
+    let cancel = CancellationToken::cancel_on_ctrl_c()?;
     
     create_dir_all("output/lab7")?;
     
@@ -96,24 +110,43 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         false, 
     );
     
-    let hae_baseline_algo = Hae::new(base_ls_edge.clone(), 20, 40, true);
+    let hae_baseline_algo = Hae::new(
+        base_ls_edge.clone(),
+        20,
+        true,
+        true,
+        Box::new(UniformSelection),
+        RecombinationType::EdgePreserving.operator(&base_ls_edge),
+        Box::new(ReplaceFromWorst::new(40)),
+    );
     let msls_baseline_algo = Msls::new(base_ls_edge.clone(), 200);
     
     let enhanced_hae_adaptive_or_opt_algo = EnhancedHaeOrOpt::new(
-        20, 40, true, 5, false, 
+        20,
+        true,
+        false,
+        Box::new(TournamentSelection::new(3)),
+        Box::new(DestroyRepairRecombination::new(0.15, Some(5))),
+        Box::new(EliteAwareReplacement::new(5, 40)),
     );
     
     // Store AlgoRunStats for each algorithm and instance
     let mut results: HashMap<String, HashMap<String, AlgoRunStats>> = HashMap::new();
 
     for (instance_name, instance_arc) in &instances {
+        if cancel.is_cancelled() {
+            break;
+        }
         println!("\n=== Processing instance: {} ===", instance_name);
         results.insert(instance_name.clone(), HashMap::new());
 
         // --- MSLS Baseline (run once to get time limit, but store its stats too) ---
         println!("\n1. Measuring MSLS performance (1 run for time limit determination)...");
         let msls_start_time = Instant::now();
-        let msls_solution = msls_baseline_algo.solve_with_feedback(instance_arc, &mut |_| {});
+        let msls_termination = Termination::MaxIterations(msls_baseline_algo.num_starts)
+            .or(Termination::Cancelled(cancel.clone()));
+        let msls_outcome = msls_baseline_algo.solve_budgeted(instance_arc, &msls_termination, &mut |_| {});
+        let msls_solution = msls_outcome.solution;
         let msls_run_time = msls_start_time.elapsed();
         let msls_cost = msls_solution.calculate_cost(instance_arc);
         let msls_time_limit = msls_run_time; // Use this single run time as the limit for others
@@ -122,71 +155,87 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // For MSLS, we effectively do 1 run in terms of how its num_starts works internally.
         // The time reported by MSLS is its total execution time for all its internal starts.
         // We store this single observation.
-        msls_stats.add_run(msls_cost, msls_run_time, msls_baseline_algo.num_starts); // num_starts as iterations proxy
+        msls_stats.add_run(msls_cost, msls_run_time, msls_outcome.iterations); // completed starts as iterations
         results.get_mut(instance_name).unwrap().insert("MSLS".to_string(), msls_stats);
         println!("  MSLS completed in {:?} with cost {}. Time limit for other algos: {:?}", msls_run_time, msls_cost, msls_time_limit);
 
-        // --- HAE Baseline (multiple runs) ---
-        println!("\n2. Running HAE_baseline ({} runs) with time limit {:?}...", NUM_RUNS, msls_time_limit);
-        let mut hae_stats = AlgoRunStats::new();
-        let mut best_hae_solution_for_plot: Option<Solution> = None;
-        let mut best_hae_cost_for_plot = i32::MAX;
-
-        for run in 0..NUM_RUNS {
-            let run_start_time = Instant::now();
-            let (hae_solution, hae_iterations) = hae_baseline_algo.solve_timed(instance_arc, msls_time_limit, &mut |_| {});
-            let hae_run_time = run_start_time.elapsed();
-            let hae_cost = hae_solution.calculate_cost(instance_arc);
-            hae_stats.add_run(hae_cost, hae_run_time, hae_iterations);
-            println!("  HAE Run {}/{}: cost {}, time {:?}", run + 1, NUM_RUNS, hae_cost, hae_run_time);
-            if hae_cost < best_hae_cost_for_plot {
-                best_hae_cost_for_plot = hae_cost;
-                best_hae_solution_for_plot = Some(hae_solution);
+        // --- Time-budgeted algorithms (multiple runs each) ---
+        let termination = Termination::TimeLimit(msls_time_limit)
+            .or(Termination::Cancelled(cancel.clone()));
+        let budgeted_algos: [(&str, &str, &(dyn BudgetedAlgorithm + Send + Sync)); 2] = [
+            ("HAE_baseline", "HAE_baseline", &hae_baseline_algo),
+            (enhanced_hae_adaptive_or_opt_algo.name(), "EnhancedHAEOrOpt", &enhanced_hae_adaptive_or_opt_algo),
+        ];
+
+        let mut convergence: Vec<(&str, Vec<ConvergenceTrace>)> = Vec::new();
+        for (step, (algo_label, plot_tag, algo)) in budgeted_algos.iter().enumerate() {
+            println!("\n{}. Running {} ({} runs) with time limit {:?}...", step + 2, algo_label, NUM_RUNS, msls_time_limit);
+            let mut algo_stats = AlgoRunStats::new();
+            let mut best_solution_for_plot: Option<Solution> = None;
+            let mut best_cost_for_plot = i32::MAX;
+            let mut traces = Vec::with_capacity(NUM_RUNS);
+
+            for run in 0..NUM_RUNS {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                let run_start_time = Instant::now();
+                let mut recorder = TraceRecorder::new();
+                let outcome = algo.solve_budgeted(instance_arc, &termination, &mut |event| recorder.record(&event));
+                traces.push(recorder.finish());
+                let cancelled = outcome.stop_reason == StopReason::Cancelled;
+                let (solution, iterations) = (outcome.solution, outcome.iterations);
+                let run_time = run_start_time.elapsed();
+                let cost = solution.calculate_cost(instance_arc);
+                algo_stats.add_run(cost, run_time, iterations);
+                println!("  {} Run {}/{}: cost {}, time {:?}{}", algo_label, run + 1, NUM_RUNS, cost, run_time, if cancelled { " (cancelled)" } else { "" });
+                if cost < best_cost_for_plot {
+                    best_cost_for_plot = cost;
+                    best_solution_for_plot = Some(solution);
+                }
             }
-        }
-        results.get_mut(instance_name).unwrap().insert("HAE_baseline".to_string(), hae_stats);
-        if let Some(sol) = best_hae_solution_for_plot {
-            let plot_title = format!("{} - HAE_baseline (Best of {} runs) - Cost: {}", instance_name, NUM_RUNS, best_hae_cost_for_plot);
-            let plot_path_str = format!("output/lab7/{}_HAE_baseline_best.png", instance_name);
-            plot_solution(&**instance_arc, &sol, &plot_title, Path::new(&plot_path_str))?;
-            println!("  HAE best solution plot saved to: {}", plot_path_str);
-        }
-        
-        // --- EnhancedHaeOrOpt (multiple runs) ---
-        println!("\n3. Running {} ({} runs) with time limit {:?}...", enhanced_hae_adaptive_or_opt_algo.name(), NUM_RUNS, msls_time_limit);
-        let mut enhanced_stats = AlgoRunStats::new();
-        let mut best_enhanced_solution_for_plot: Option<Solution> = None;
-        let mut best_enhanced_cost_for_plot = i32::MAX;
-
-        for run in 0..NUM_RUNS {
-            let run_start_time = Instant::now();
-            let (enhanced_solution, enhanced_iterations, _) = enhanced_hae_adaptive_or_opt_algo.solve_timed(instance_arc, msls_time_limit, &mut |_| {});
-            let enhanced_run_time = run_start_time.elapsed();
-            let enhanced_cost = enhanced_solution.calculate_cost(instance_arc);
-            enhanced_stats.add_run(enhanced_cost, enhanced_run_time, enhanced_iterations);
-            println!("  {} Run {}/{}: cost {}, time {:?}", enhanced_hae_adaptive_or_opt_algo.name(), run + 1, NUM_RUNS, enhanced_cost, enhanced_run_time);
-            if enhanced_cost < best_enhanced_cost_for_plot {
-                best_enhanced_cost_for_plot = enhanced_cost;
-                best_enhanced_solution_for_plot = Some(enhanced_solution);
+            results.get_mut(instance_name).unwrap().insert(algo_label.to_string(), algo_stats);
+            if let Some(sol) = best_solution_for_plot {
+                let plot_title = format!("{} - {} (Best of {} runs) - Cost: {}", instance_name, algo_label, NUM_RUNS, best_cost_for_plot);
+                let plot_path_str = format!("output/lab7/{}_{}_best.png", instance_name, plot_tag);
+                plot_solution(&**instance_arc, &sol, &plot_title, Path::new(&plot_path_str))?;
+                println!("  {} best solution plot saved to: {}", algo_label, plot_path_str);
             }
+            let csv_path_str = format!("output/lab7/{}_{}_convergence.csv", instance_name, plot_tag);
+            write_traces_csv(Path::new(&csv_path_str), algo_label, &traces)?;
+            println!("  {} convergence traces saved to: {}", algo_label, csv_path_str);
+            convergence.push((plot_tag, traces));
         }
-        results.get_mut(instance_name).unwrap().insert(enhanced_hae_adaptive_or_opt_algo.name().to_string(), enhanced_stats);
-        if let Some(sol) = best_enhanced_solution_for_plot {
-            let plot_title = format!("{} - {} (Best of {} runs) - Cost: {}", instance_name, enhanced_hae_adaptive_or_opt_algo.name(), NUM_RUNS, best_enhanced_cost_for_plot);
-            let plot_path_str = format!("output/lab7/{}_EnhancedHAEOrOpt_best.png", instance_name);
-            plot_solution(&**instance_arc, &sol, &plot_title, Path::new(&plot_path_str))?;
-            println!("  {} best solution plot saved to: {}", enhanced_hae_adaptive_or_opt_algo.name(), plot_path_str);
+
+        if !convergence.is_empty() {
+            let series: Vec<(&str, &[ConvergenceTrace])> = convergence
+                .iter()
+                .map(|(tag, traces)| (*tag, traces.as_slice()))
+                .collect();
+            let plot_title = format!("{} - convergence (mean, min/max over {} runs)", instance_name, NUM_RUNS);
+            let plot_path_str = format!("output/lab7/{}_convergence.png", instance_name);
+            match plot_convergence(&series, &plot_title, Path::new(&plot_path_str)) {
+                Ok(()) => println!("  Convergence plot saved to: {}", plot_path_str),
+                Err(e) => eprintln!("  Could not plot convergence for {}: {}", instance_name, e),
+            }
         }
     }
     
     println!("\n\n=== PODSUMOWANIE WYNIKÓW ({} URUCHOMIEŃ) ===", NUM_RUNS);
-    println!("{:<15} {:<70} {:<15} {:<15} {:<15} {:<15} {:<20}", "Instance", "Algorithm", "Avg Cost", "Min Cost", "Std Dev", "Avg Time", "Avg Impr. vs HAE");
-    println!("{}", "-".repeat(165));
+    println!("{:<15} {:<70} {:<15} {:<15} {:<15} {:<15} {:<20} {:<20}", "Instance", "Algorithm", "Avg Cost", "Min Cost", "Std Dev", "Avg Time", "Avg Impr. vs HAE", "Gap to LB min (avg)");
+    println!("{}", "-".repeat(186));
     
     for instance_name_str in &instance_files {
         let instance_key = instance_name_str.to_string();
         if let Some(instance_results) = results.get(&instance_key) {
             let hae_avg_cost = instance_results.get("HAE_baseline").map_or(0.0, |s| s.avg_cost());
+            // The best cost found by any algorithm steers the subgradient step size
+            let lower_bound = instance_results
+                .values()
+                .filter_map(|s| s.min_cost())
+                .min()
+                .zip(instances.get(&instance_key))
+                .map(|(best_cost, instance)| best_lower_bound(instance, best_cost));
 
             let algo_keys = vec!["MSLS".to_string(), "HAE_baseline".to_string(), enhanced_hae_adaptive_or_opt_algo.name().to_string()];
 
@@ -203,15 +252,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     } else { 
                         0.0 // Avoid division by zero if hae_avg_cost is 0, or for MSLS vs itself initially
                     };
+                    let gap = match lower_bound {
+                        Some(bound) => format!(
+                            "{:.2}% ({:.2}%)",
+                            gap_percent(min_cost as f64, bound),
+                            gap_percent(avg_cost, bound)
+                        ),
+                        None => "N/A".to_string(),
+                    };
                     println!(
-                        "{: <15} {: <70} {: <15.2} {: <15} {: <15.2} {: <15.3?} {: <+20.2}%",
+                        "{: <15} {: <70} {: <15.2} {: <15} {: <15.2} {: <15.3?} {: <+20.2}% {: <20}",
                         instance_key,
                         algo_name_key,
                         avg_cost,
                         min_cost,
                         std_dev,
                         avg_time,
-                        improvement
+                        improvement,
+                        gap
                     );
                 }
             }
@@ -222,6 +280,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nInterpretacja wyników:");
     println!("- Prezentowane są średnie, minimalne koszty i odch. std. z {} uruchomień.", NUM_RUNS);
     println!("- Poprawa jest liczona względem średniego kosztu HAE_baseline.");
+    println!("- Luka (gap) jest liczona względem dolnego ograniczenia kosztu (2-matching / Held-Karp).");
     
     Ok(())
 } 
\ No newline at end of file
@@ -4,4 +4,10 @@ pub mod tasks;
 pub mod tsplib;
 pub mod algorithm;
 pub mod utils;
-pub mod moves; 
\ No newline at end of file
+pub mod global_convexity;
+pub mod ilp;
+pub mod lower_bound;
+pub mod moves;
+pub mod termination;
+pub mod trace;
+pub mod visualization;
@@ -0,0 +1,261 @@
+//! Lower bounds on the cost of two disjoint cycles covering all nodes.
+//!
+//! Both bounds relax the balance constraint and the requirement of exactly two cycles, so
+//! they hold for every feasible solution and can be used to report optimality gaps.
+
+use crate::tsplib::TsplibInstance;
+
+/// Subgradient iterations used by `best_lower_bound`.
+pub const DEFAULT_SUBGRADIENT_ITERATIONS: usize = 1000;
+/// The step multiplier is halved after this many iterations without a better bound.
+const STEP_HALVING_PERIOD: usize = 30;
+/// Subgradient optimization stops once the step multiplier falls below this value.
+const MIN_STEP_MULTIPLIER: f64 = 1e-4;
+
+/// Two-matching (assignment) bound.
+///
+/// Orienting both cycles gives every node exactly one successor and one predecessor, so a
+/// solution is a perfect matching between the outgoing and the incoming copies of the
+/// nodes that never matches a node with itself. The cheapest such matching, found with the
+/// Hungarian method in `O(n^3)`, is a lower bound.
+pub fn two_matching_bound(instance: &TsplibInstance) -> i32 {
+    let n = instance.size();
+    if n < 2 {
+        return 0;
+    }
+    let forbidden = i64::MAX / 4;
+    let cost = |i: usize, j: usize| -> i64 {
+        if i == j {
+            forbidden
+        } else {
+            instance.distance(i, j) as i64
+        }
+    };
+
+    // Shortest augmenting path formulation with row potentials `u` and column potentials
+    // `v`; rows and columns are 1-based, column 0 is the virtual start.
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; n + 1];
+    let mut matched_row = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+    for row in 1..=n {
+        matched_row[0] = row;
+        let mut column = 0;
+        let mut min_slack = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current_row = matched_row[column];
+            let mut delta = i64::MAX;
+            let mut next_column = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost(current_row - 1, j - 1) - u[current_row] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = column;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    next_column = j;
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[matched_row[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            column = next_column;
+            if matched_row[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let previous = way[column];
+            matched_row[column] = matched_row[previous];
+            column = previous;
+        }
+    }
+
+    (1..=n)
+        .map(|j| cost(matched_row[j] - 1, j - 1))
+        .sum::<i64>() as i32
+}
+
+/// Held-Karp style bound adapted to two cycles.
+///
+/// Removing one edge from each cycle leaves a spanning forest with two trees, so every
+/// solution is such a forest plus two more edges. The cheapest structure of that kind is
+/// the minimum spanning tree without its heaviest edge plus the two cheapest edges outside
+/// the forest. Subgradient optimization of Lagrangian node penalties then pushes every
+/// node towards degree 2. `upper_bound` (e.g. the best known cost) sets the step size.
+///
+/// Below six nodes one cycle has at most two nodes and uses its single edge twice, which the
+/// forest structure cannot represent; the two-matching bound is returned instead.
+pub fn held_karp_bound(instance: &TsplibInstance, upper_bound: i32, max_iterations: usize) -> i32 {
+    let n = instance.size();
+    if n < 6 {
+        return two_matching_bound(instance);
+    }
+    let mut penalties = vec![0.0f64; n];
+    let mut best_bound = f64::MIN;
+    let mut step_multiplier = 2.0;
+    let mut iterations_without_improvement = 0;
+
+    for _ in 0..max_iterations.max(1) {
+        let (structure_cost, degrees) = two_forest_plus_two_edges(instance, &penalties);
+        let bound = structure_cost - 2.0 * penalties.iter().sum::<f64>();
+        if bound > best_bound + 1e-9 {
+            best_bound = bound;
+            iterations_without_improvement = 0;
+        } else {
+            iterations_without_improvement += 1;
+            if iterations_without_improvement >= STEP_HALVING_PERIOD {
+                step_multiplier /= 2.0;
+                iterations_without_improvement = 0;
+            }
+        }
+
+        let squared_norm: f64 = degrees.iter().map(|&d| ((d - 2) * (d - 2)) as f64).sum();
+        if squared_norm == 0.0 || step_multiplier < MIN_STEP_MULTIPLIER {
+            break;
+        }
+        let step = step_multiplier * (upper_bound as f64 - bound).max(1.0) / squared_norm;
+        for (penalty, &degree) in penalties.iter_mut().zip(&degrees) {
+            *penalty += step * (degree - 2) as f64;
+        }
+    }
+
+    // Costs are integral, so the bound can be rounded up
+    (best_bound - 1e-6).ceil() as i32
+}
+
+/// Cost and node degrees of the cheapest spanning two-tree forest plus two further edges
+/// under the penalized costs `distance(u, v) + penalties[u] + penalties[v]`.
+fn two_forest_plus_two_edges(instance: &TsplibInstance, penalties: &[f64]) -> (f64, Vec<i32>) {
+    let n = instance.size();
+    let weight = |u: usize, v: usize| instance.distance(u, v) as f64 + penalties[u] + penalties[v];
+
+    // Prim's algorithm on the complete graph
+    let mut in_tree = vec![false; n];
+    let mut parent = vec![0usize; n];
+    let mut best = vec![f64::MAX; n];
+    best[0] = 0.0;
+    let mut tree_edges: Vec<(usize, usize, f64)> = Vec::with_capacity(n - 1);
+    for _ in 0..n {
+        let u = (0..n)
+            .filter(|&v| !in_tree[v])
+            .min_by(|&a, &b| best[a].total_cmp(&best[b]))
+            .expect("a node outside the tree remains");
+        in_tree[u] = true;
+        if u != 0 {
+            tree_edges.push((parent[u], u, best[u]));
+        }
+        for v in 0..n {
+            if !in_tree[v] {
+                let w = weight(u, v);
+                if w < best[v] {
+                    best[v] = w;
+                    parent[v] = u;
+                }
+            }
+        }
+    }
+
+    let heaviest = (0..tree_edges.len())
+        .max_by(|&a, &b| tree_edges[a].2.total_cmp(&tree_edges[b].2))
+        .expect("n >= 2");
+    tree_edges.swap_remove(heaviest);
+
+    let mut in_forest = vec![false; n * n];
+    let mut degrees = vec![0i32; n];
+    let mut cost = 0.0;
+    for &(u, v, w) in &tree_edges {
+        in_forest[u * n + v] = true;
+        in_forest[v * n + u] = true;
+        degrees[u] += 1;
+        degrees[v] += 1;
+        cost += w;
+    }
+
+    // Two cheapest edges outside the forest
+    let mut extra: [Option<(f64, usize, usize)>; 2] = [None, None];
+    for u in 0..n {
+        for v in u + 1..n {
+            if in_forest[u * n + v] {
+                continue;
+            }
+            let candidate = (weight(u, v), u, v);
+            if extra[0].is_none_or(|e| candidate.0 < e.0) {
+                extra[1] = extra[0];
+                extra[0] = Some(candidate);
+            } else if extra[1].is_none_or(|e| candidate.0 < e.0) {
+                extra[1] = Some(candidate);
+            }
+        }
+    }
+    for (w, u, v) in extra.into_iter().flatten() {
+        degrees[u] += 1;
+        degrees[v] += 1;
+        cost += w;
+    }
+    (cost, degrees)
+}
+
+/// The stronger of the two bounds.
+pub fn best_lower_bound(instance: &TsplibInstance, upper_bound: i32) -> i32 {
+    two_matching_bound(instance).max(held_karp_bound(
+        instance,
+        upper_bound,
+        DEFAULT_SUBGRADIENT_ITERATIONS,
+    ))
+}
+
+/// Relative gap of `cost` above `lower_bound`, in percent.
+pub fn gap_percent(cost: f64, lower_bound: i32) -> f64 {
+    if lower_bound <= 0 {
+        return 0.0;
+    }
+    (cost - lower_bound as f64) / lower_bound as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::BudgetedAlgorithm;
+    use crate::algorithms::exact::ExactSolver;
+    use crate::termination::Termination;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_instance(n: usize, rng: &mut StdRng) -> TsplibInstance {
+        let coordinates = (0..n)
+            .map(|_| (rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect();
+        TsplibInstance::from_coordinates("random", coordinates)
+    }
+
+    #[test]
+    fn bounds_never_exceed_the_optimum() {
+        let mut rng = StdRng::seed_from_u64(48);
+        for n in 6..=12 {
+            let instance = random_instance(n, &mut rng);
+            let optimum = ExactSolver::new()
+                .solve_budgeted(&instance, &Termination::Any(Vec::new()), &mut |_| {})
+                .solution
+                .calculate_cost(&instance);
+            assert!(two_matching_bound(&instance) <= optimum, "n = {}", n);
+            assert!(
+                held_karp_bound(&instance, optimum, DEFAULT_SUBGRADIENT_ITERATIONS) <= optimum,
+                "n = {}",
+                n
+            );
+            assert!(best_lower_bound(&instance, optimum) <= optimum, "n = {}", n);
+        }
+    }
+}
@@ -1,7 +1,11 @@
 mod algorithm;
 mod algorithms;
 mod global_convexity;
+mod ilp;
+mod lower_bound;
 mod moves;
+mod termination;
+mod trace;
 mod tsplib;
 mod utils;
 mod visualization;
@@ -9,17 +13,26 @@ mod visualization;
 use algorithms::local_search::base::{
     InitialSolutionType, LocalSearch, NeighborhoodType, SearchVariant,
 };
+use algorithms::evolutionary::replacement::ReplaceFromWorst;
+use algorithms::evolutionary::selection::UniformSelection;
 use algorithms::hae::Hae;
+use algorithms::recombination::RecombinationType;
 use global_convexity::{analyze_global_convexity, plot_convexity_analysis};
+use lower_bound::{best_lower_bound, gap_percent};
+use termination::{CancellationToken, Termination};
 use std::collections::HashMap;
 use std::fs::create_dir_all;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tsplib::TsplibInstance;
 use std::fs::OpenOptions;
 use std::io::Write;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Ctrl+C stops the running analysis and keeps what was computed so far
+    let cancel = CancellationToken::cancel_on_ctrl_c()?;
+
     println!("Loading instances...");
 
     create_dir_all("output")?;
@@ -52,21 +65,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // The Hae::new constructor now handles its internal LS verify flags.
     // The base_ls passed here is for its main LS step; if it involved OrOpt/ThreeOpt, it should be pre-configured.
     // Since base_ls here is EdgeExchange, its verify_delta_computationally=false is appropriate.
-    let best_algorithm = Hae::new(base_ls.clone(), 20, 40, false); 
+    let best_algorithm = Hae::new(
+        base_ls.clone(),
+        20,
+        false,
+        true,
+        Box::new(UniformSelection),
+        RecombinationType::EdgePreserving.operator(&base_ls),
+        Box::new(ReplaceFromWorst::new(40)),
+    );
 
     println!("\n=== ZADANIE 6: TESTY GLOBALNEJ WYPUKŁOŚCI ===");
 
     for (name, instance) in &instances {
+        if cancel.is_cancelled() {
+            println!("\nCancelled, skipping instance: {}", name);
+            continue;
+        }
         println!("\nProcessing instance: {}", name);
 
         // --- Global Convexity Analysis (Lab 6) ---
         println!("  Running Global Convexity Analysis...");
         
+        // Same time limit HAE uses by default
+        let best_time_limit = Duration::from_secs_f64(instance.dimension as f64 / 100.0);
         let convexity_result = analyze_global_convexity(
             instance,
             name,
             &best_algorithm,
+            &Termination::TimeLimit(best_time_limit),
             &base_ls,
+            &cancel,
             &mut |s| println!("    [Convexity] {}", s),
         );
         
@@ -105,6 +134,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )?;
         
         println!("    Global Convexity Analysis completed for {}", name);
+        let lower_bound = best_lower_bound(instance, convexity_result.best_cost);
+        let gap = gap_percent(convexity_result.best_cost as f64, lower_bound);
+        println!("      Best cost: {} (lower bound: {}, gap: {:.2}%)", convexity_result.best_cost, lower_bound, gap);
         println!("      Correlation (S_best - Vertex Pairs): {:.4}", convexity_result.correlation_vertex_pairs);
         println!("      Correlation (S_best - Common Edges): {:.4}", convexity_result.correlation_common_edges);
         println!("      Correlation (Avg. Others - Vertex Pairs): {:.4}", convexity_result.correlation_avg_vertex_pairs);
@@ -115,6 +147,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Append results to text file
         let mut file_appender = OpenOptions::new().append(true).create(true).open(results_file_path)?;
         writeln!(file_appender, "Instance: {}", name)?;
+        writeln!(file_appender, "  Best cost: {} (lower bound: {}, gap: {:.2}%)", convexity_result.best_cost, lower_bound, gap)?;
         writeln!(file_appender, "  Correlation (S_best - Vertex Pairs): {:.4}", convexity_result.correlation_vertex_pairs)?;
         writeln!(file_appender, "  Correlation (S_best - Common Edges): {:.4}", convexity_result.correlation_common_edges)?;
         writeln!(file_appender, "  Correlation (Avg. Others - Vertex Pairs): {:.4}", convexity_result.correlation_avg_vertex_pairs)?;
@@ -1,9 +1,9 @@
 use crate::moves::types::{CycleId, EvaluatedMove, Move};
-use crate::tsplib::{Solution, TsplibInstance};
+use crate::tsplib::{EdgeCost, Solution};
 
-pub fn evaluate_inter_route_exchange(
+pub fn evaluate_inter_route_exchange<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     pos1: usize, // Position of node u in cycle 1
     pos2: usize, // Position of node v in cycle 2
 ) -> Option<EvaluatedMove> {
@@ -32,10 +32,10 @@ pub fn evaluate_inter_route_exchange(
         // Delta = (dist(prev_v, u) + dist(u, next_v)) - (dist(prev_v, v) + dist(v, next_v))
         // If n2 == 2, prev_v == next_v, delta = 2*dist(prev_v, u) - 2*dist(prev_v, v)
         if n2 == 2 {
-            2 * instance.distance(prev_v, u) - 2 * instance.distance(prev_v, v)
+            2 * costs.edge_cost(prev_v, u) - 2 * costs.edge_cost(prev_v, v)
         } else {
-            (instance.distance(prev_v, u) + instance.distance(u, next_v))
-                - (instance.distance(prev_v, v) + instance.distance(v, next_v))
+            (costs.edge_cost(prev_v, u) + costs.edge_cost(u, next_v))
+                - (costs.edge_cost(prev_v, v) + costs.edge_cost(v, next_v))
         }
     } else if n2 == 1 {
         // Cycle 2 has only node v
@@ -45,10 +45,10 @@ pub fn evaluate_inter_route_exchange(
         // Delta = (dist(prev_u, v) + dist(v, next_u)) - (dist(prev_u, u) + dist(u, next_u))
         // If n1 == 2, prev_u == next_u, delta = 2*dist(prev_u, v) - 2*dist(prev_u, u)
         if n1 == 2 {
-            2 * instance.distance(prev_u, v) - 2 * instance.distance(prev_u, u)
+            2 * costs.edge_cost(prev_u, v) - 2 * costs.edge_cost(prev_u, u)
         } else {
-            (instance.distance(prev_u, v) + instance.distance(v, next_u))
-                - (instance.distance(prev_u, u) + instance.distance(u, next_u))
+            (costs.edge_cost(prev_u, v) + costs.edge_cost(v, next_u))
+                - (costs.edge_cost(prev_u, u) + costs.edge_cost(u, next_u))
         }
     } else {
         // Both cycles have >= 2 nodes
@@ -60,19 +60,19 @@ pub fn evaluate_inter_route_exchange(
         // Calculate cost change in Cycle 1 (replace u with v)
         let delta_c1 = if n1 == 2 {
             // remove 2*dist(prev_u, u), add 2*dist(prev_u, v)
-            2 * instance.distance(prev_u, v) - 2 * instance.distance(prev_u, u)
+            2 * costs.edge_cost(prev_u, v) - 2 * costs.edge_cost(prev_u, u)
         } else {
-            (instance.distance(prev_u, v) + instance.distance(v, next_u))
-                - (instance.distance(prev_u, u) + instance.distance(u, next_u))
+            (costs.edge_cost(prev_u, v) + costs.edge_cost(v, next_u))
+                - (costs.edge_cost(prev_u, u) + costs.edge_cost(u, next_u))
         };
 
         // Calculate cost change in Cycle 2 (replace v with u)
         let delta_c2 = if n2 == 2 {
             // remove 2*dist(prev_v, v), add 2*dist(prev_v, u)
-            2 * instance.distance(prev_v, u) - 2 * instance.distance(prev_v, v)
+            2 * costs.edge_cost(prev_v, u) - 2 * costs.edge_cost(prev_v, v)
         } else {
-            (instance.distance(prev_v, u) + instance.distance(u, next_v))
-                - (instance.distance(prev_v, v) + instance.distance(v, next_v))
+            (costs.edge_cost(prev_v, u) + costs.edge_cost(u, next_v))
+                - (costs.edge_cost(prev_v, v) + costs.edge_cost(v, next_v))
         };
 
         delta_c1 + delta_c2
@@ -1,9 +1,9 @@
 use crate::moves::types::{CycleId, EvaluatedMove, Move};
-use crate::tsplib::{Solution, TsplibInstance};
+use crate::tsplib::{EdgeCost, Solution};
 
-pub fn evaluate_intra_route_vertex_exchange(
+pub fn evaluate_intra_route_vertex_exchange<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     cycle: CycleId,
     pos1: usize,
     pos2: usize,
@@ -28,7 +28,9 @@ pub fn evaluate_intra_route_vertex_exchange(
         0
     } else if pos2 == pos1 + 1 || (pos1 == 0 && pos2 == n - 1) {
         // Adjacent nodes (including wrap-around)
-        // Find neighbours correctly considering wrap-around for both cases
+        // On wrap-around v2 precedes v1, so swap roles to keep v1 before v2
+        let (pos1, pos2) = if pos2 == pos1 + 1 { (pos1, pos2) } else { (pos2, pos1) };
+        let (v1, v2) = (cycle_vec[pos1], cycle_vec[pos2]);
         let prev1 = cycle_vec[if pos1 == 0 { n - 1 } else { pos1 - 1 }];
         let next2 = cycle_vec[(pos2 + 1) % n]; // next of v2
 
@@ -36,10 +38,10 @@ pub fn evaluate_intra_route_vertex_exchange(
         // Edges removed: (prev1, v1), (v1, v2), (v2, next2)
         // Edges added:   (prev1, v2), (v2, v1), (v1, next2)
         // Delta = Added - Removed
-        (instance.distance(prev1, v2) + instance.distance(v2, v1) + instance.distance(v1, next2))
-            - (instance.distance(prev1, v1)
-                + instance.distance(v1, v2)
-                + instance.distance(v2, next2))
+        (costs.edge_cost(prev1, v2) + costs.edge_cost(v2, v1) + costs.edge_cost(v1, next2))
+            - (costs.edge_cost(prev1, v1)
+                + costs.edge_cost(v1, v2)
+                + costs.edge_cost(v2, next2))
     } else {
         // Non-adjacent nodes
         let prev1 = cycle_vec[if pos1 == 0 { n - 1 } else { pos1 - 1 }];
@@ -50,14 +52,14 @@ pub fn evaluate_intra_route_vertex_exchange(
         // Edges removed: (prev1, v1), (v1, next1), (prev2, v2), (v2, next2)
         // Edges added:   (prev1, v2), (v2, next1), (prev2, v1), (v1, next2)
         // Delta = Added - Removed
-        (instance.distance(prev1, v2)
-            + instance.distance(v2, next1)
-            + instance.distance(prev2, v1)
-            + instance.distance(v1, next2))
-            - (instance.distance(prev1, v1)
-                + instance.distance(v1, next1)
-                + instance.distance(prev2, v2)
-                + instance.distance(v2, next2))
+        (costs.edge_cost(prev1, v2)
+            + costs.edge_cost(v2, next1)
+            + costs.edge_cost(prev2, v1)
+            + costs.edge_cost(v1, next2))
+            - (costs.edge_cost(prev1, v1)
+                + costs.edge_cost(v1, next1)
+                + costs.edge_cost(prev2, v2)
+                + costs.edge_cost(v2, next2))
     };
 
     Some(EvaluatedMove {
@@ -73,9 +75,9 @@ pub fn evaluate_intra_route_vertex_exchange(
 ///
 /// Assumes `pos1` and `pos2` represent the *start* indices of the edges to be removed.
 /// Returns `None` if the move is invalid (e.g., cycle size < 3, adjacent edges).
-pub fn evaluate_intra_route_edge_exchange(
+pub fn evaluate_intra_route_edge_exchange<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     cycle: CycleId,
     pos1: usize, // Index of node `a`
     pos2: usize, // Index of node `c`
@@ -103,10 +105,10 @@ pub fn evaluate_intra_route_edge_exchange(
     let d = cycle_vec[(pos2 + 1) % n];
 
     // Cost removed: dist(a, b) + dist(c, d)
-    let cost_removed = instance.distance(a, b) + instance.distance(c, d);
+    let cost_removed = costs.edge_cost(a, b) + costs.edge_cost(c, d);
 
     // Cost added: dist(a, c) + dist(b, d)
-    let cost_added = instance.distance(a, c) + instance.distance(b, d);
+    let cost_added = costs.edge_cost(a, c) + costs.edge_cost(b, d);
 
     let delta = cost_added - cost_removed;
 
@@ -122,9 +124,9 @@ pub fn evaluate_intra_route_edge_exchange(
 /// 2-opt move by removing edges (a, a_next) and (b, b_next), and adding
 /// edges (a, b) and (a_next, b_next).
 /// `pos_a` is the index of node `a`, `pos_b` is the index of node `b`.
-pub fn evaluate_candidate_intra_route_edge_exchange(
+pub fn evaluate_candidate_intra_route_edge_exchange<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     cycle_id: CycleId,
     pos_a: usize,
     pos_b: usize,
@@ -153,10 +155,10 @@ pub fn evaluate_candidate_intra_route_edge_exchange(
     let b_next = cycle_vec[pos_b_next];
 
     // Cost removed: dist(a, a_next) + dist(b, b_next)
-    let cost_removed = instance.distance(a, a_next) + instance.distance(b, b_next);
+    let cost_removed = costs.edge_cost(a, a_next) + costs.edge_cost(b, b_next);
 
     // Cost added: dist(a, b) + dist(a_next, b_next)
-    let cost_added = instance.distance(a, b) + instance.distance(a_next, b_next);
+    let cost_added = costs.edge_cost(a, b) + costs.edge_cost(a_next, b_next);
 
     let delta = cost_added - cost_removed;
 
@@ -176,9 +178,9 @@ pub fn evaluate_candidate_intra_route_edge_exchange(
 }
 
 /// Evaluate 3-opt move - removes 3 edges and reconnects in the best way
-pub fn evaluate_intra_route_3opt(
+pub fn evaluate_intra_route_3opt<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     cycle_id: CycleId,
     pos1: usize,
     pos2: usize,
@@ -210,14 +212,14 @@ pub fn evaluate_intra_route_3opt(
     let f = cycle[(pos3 + 1) % n];
     
     // Current cost of the 3 edges
-    let current_cost = instance.distance(a, b) + instance.distance(c, d) + instance.distance(e, f);
+    let current_cost = costs.edge_cost(a, b) + costs.edge_cost(c, d) + costs.edge_cost(e, f);
     
     // Try all possible reconnections (excluding the current one)
     let mut best_delta = 0;
     let mut best_case = 0;
     
     // Case 1: a-c, b-e, d-f (reverse segment 1)
-    let case1_cost = instance.distance(a, c) + instance.distance(b, e) + instance.distance(d, f);
+    let case1_cost = costs.edge_cost(a, c) + costs.edge_cost(b, e) + costs.edge_cost(d, f);
     let case1_delta = case1_cost - current_cost;
     if case1_delta < best_delta {
         best_delta = case1_delta;
@@ -225,7 +227,7 @@ pub fn evaluate_intra_route_3opt(
     }
     
     // Case 2: a-e, f-c, d-b (reverse segment 2)
-    let case2_cost = instance.distance(a, e) + instance.distance(f, c) + instance.distance(d, b);
+    let case2_cost = costs.edge_cost(a, e) + costs.edge_cost(f, c) + costs.edge_cost(d, b);
     let case2_delta = case2_cost - current_cost;
     if case2_delta < best_delta {
         best_delta = case2_delta;
@@ -233,7 +235,7 @@ pub fn evaluate_intra_route_3opt(
     }
     
     // Case 3: a-d, e-b, f-c (reverse segment 3)
-    let case3_cost = instance.distance(a, d) + instance.distance(e, b) + instance.distance(f, c);
+    let case3_cost = costs.edge_cost(a, d) + costs.edge_cost(e, b) + costs.edge_cost(f, c);
     let case3_delta = case3_cost - current_cost;
     if case3_delta < best_delta {
         best_delta = case3_delta;
@@ -241,7 +243,7 @@ pub fn evaluate_intra_route_3opt(
     }
     
     // Case 4: a-d, e-c, f-b (reverse segments 1 and 2)
-    let case4_cost = instance.distance(a, d) + instance.distance(e, c) + instance.distance(f, b);
+    let case4_cost = costs.edge_cost(a, d) + costs.edge_cost(e, c) + costs.edge_cost(f, b);
     let case4_delta = case4_cost - current_cost;
     if case4_delta < best_delta {
         best_delta = case4_delta;
@@ -265,9 +267,9 @@ pub fn evaluate_intra_route_3opt(
 }
 
 /// Evaluate Or-opt move - relocate a chain of k nodes
-pub fn evaluate_intra_route_or_opt(
+pub fn evaluate_intra_route_or_opt<C: EdgeCost + ?Sized>(
     solution: &Solution,
-    instance: &TsplibInstance,
+    costs: &C,
     cycle_id: CycleId,
     from_pos: usize,
     chain_length: usize,
@@ -311,13 +313,13 @@ pub fn evaluate_intra_route_or_opt(
     let ip_node = cycle_vec[ip_idx];
     let in_node = cycle_vec[in_idx];
 
-    let cost_removed = instance.distance(p_node, s1_node) +
-                       instance.distance(sk_node, n_node) +
-                       instance.distance(ip_node, in_node);
+    let cost_removed = costs.edge_cost(p_node, s1_node) +
+                       costs.edge_cost(sk_node, n_node) +
+                       costs.edge_cost(ip_node, in_node);
 
-    let cost_added = instance.distance(p_node, n_node) +
-                     instance.distance(ip_node, s1_node) +
-                     instance.distance(sk_node, in_node);
+    let cost_added = costs.edge_cost(p_node, n_node) +
+                     costs.edge_cost(ip_node, s1_node) +
+                     costs.edge_cost(sk_node, in_node);
 
     let delta = cost_added - cost_removed;
 
@@ -1,5 +1,6 @@
 pub mod inter_route;
 pub mod intra_route;
+pub mod random;
 pub mod types;
 
 pub use types::{EvaluatedMove, Move};
@@ -0,0 +1,58 @@
+use crate::algorithms::local_search::base::NeighborhoodType;
+use crate::moves::inter_route::evaluate_inter_route_exchange;
+use crate::moves::intra_route::{
+    evaluate_intra_route_edge_exchange, evaluate_intra_route_vertex_exchange,
+};
+use crate::moves::types::{CycleId, EvaluatedMove};
+use crate::tsplib::{Solution, TsplibInstance};
+use rand::Rng;
+
+/// Probability of drawing an inter-route exchange instead of an intra-route move.
+const INTER_ROUTE_PROBABILITY: f64 = 0.5;
+
+/// Draws a uniformly random move and evaluates its delta: an inter-route exchange or an
+/// intra-route move of the given `neighborhood` (`VertexExchange` or `EdgeExchange`).
+///
+/// Returns `None` when the drawn move is invalid for the solution (e.g. too short cycles);
+/// callers are expected to simply draw again.
+pub fn random_evaluated_move<R: Rng + ?Sized>(
+    solution: &Solution,
+    instance: &TsplibInstance,
+    neighborhood: NeighborhoodType,
+    rng: &mut R,
+) -> Option<EvaluatedMove> {
+    let n1 = solution.cycle1.len();
+    let n2 = solution.cycle2.len();
+    if n1 == 0 || n2 == 0 {
+        return None;
+    }
+
+    if rng.random_bool(INTER_ROUTE_PROBABILITY) {
+        let pos1 = rng.random_range(0..n1);
+        let pos2 = rng.random_range(0..n2);
+        return evaluate_inter_route_exchange(solution, instance, pos1, pos2);
+    }
+
+    let cycle = if rng.random_bool(n1 as f64 / (n1 + n2) as f64) {
+        CycleId::Cycle1
+    } else {
+        CycleId::Cycle2
+    };
+    let n = solution.get_cycle(cycle).len();
+    if n < 2 {
+        return None;
+    }
+    let pos1 = rng.random_range(0..n);
+    let pos2 = rng.random_range(0..n);
+    match neighborhood {
+        NeighborhoodType::VertexExchange => {
+            evaluate_intra_route_vertex_exchange(solution, instance, cycle, pos1, pos2)
+        }
+        NeighborhoodType::EdgeExchange => {
+            evaluate_intra_route_edge_exchange(solution, instance, cycle, pos1, pos2)
+        }
+        NeighborhoodType::ThreeOpt | NeighborhoodType::OrOpt => {
+            panic!("Random moves are only available for vertex and edge exchange neighborhoods")
+        }
+    }
+}
@@ -4,8 +4,12 @@ use std::path::Path;
 use std::time::Duration;
 
 use crate::algorithms::enhanced_hae_or_opt::EnhancedHaeOrOpt;
+use crate::algorithms::evolutionary::replacement::EliteAwareReplacement;
+use crate::algorithms::evolutionary::selection::TournamentSelection;
+use crate::algorithms::recombination::destroy_repair::DestroyRepairRecombination;
+use crate::termination::Termination;
 use crate::tsplib::TsplibInstance;
-use crate::algorithm::TspAlgorithm; // Ensure TspAlgorithm trait is in scope
+use crate::algorithm::{BudgetedAlgorithm, ProgressEvent, TspAlgorithm}; // Ensure the traits are in scope
 
 pub fn run_hae_als_analysis_task(instance_name: &str, output_dir: &str) {
     println!(
@@ -37,10 +41,11 @@ pub fn run_hae_als_analysis_task(instance_name: &str, output_dir: &str) {
 
     let algorithm = EnhancedHaeOrOpt::new(
         pop_size,
-        min_diff,
         adaptive_local_search,
-        elite_size,
         verify_or_opt_delta,
+        Box::new(TournamentSelection::new(3)),
+        Box::new(DestroyRepairRecombination::new(0.15, Some(5))),
+        Box::new(EliteAwareReplacement::new(elite_size, min_diff)),
     );
 
     // Set a time limit for the algorithm run
@@ -54,22 +59,37 @@ pub fn run_hae_als_analysis_task(instance_name: &str, output_dir: &str) {
         time_limit_seconds
     );
 
-    let mut progress_callback = |message: String| {
-        // You can choose to print progress or not
-        // println!("[Progress] {}", message);
-        if message.contains("New global best") || message.contains("Finished") {
-            println!("{}", message);
+    // (chosen operator: 0 = EdgeExchange, 1 = OrOpt; probability of EdgeExchange)
+    let mut als_history: Vec<(usize, f64)> = Vec::new();
+    let mut progress_callback = |event: ProgressEvent| match event {
+        ProgressEvent::OperatorChosen {
+            operator,
+            probability,
+            ..
+        } => {
+            if operator == "EdgeExchange" {
+                als_history.push((0, probability));
+            } else {
+                als_history.push((1, 1.0 - probability));
+            }
         }
+        // You can choose to print other progress or not
+        ProgressEvent::NewBest { .. } | ProgressEvent::Finished { .. } => println!("{}", event),
+        _ => {}
     };
 
     // Run the algorithm
-    let (best_solution, iterations, als_history) =
-        algorithm.solve_timed(&instance, time_limit, &mut progress_callback);
+    let outcome = algorithm.solve_budgeted(
+        &instance,
+        &Termination::TimeLimit(time_limit),
+        &mut progress_callback,
+    );
 
     println!(
-        "Finished run. Best solution cost: {}, Iterations: {}",
-        best_solution.calculate_cost(&instance),
-        iterations
+        "Finished run ({}). Best solution cost: {}, Iterations: {}",
+        outcome.stop_reason,
+        outcome.solution.calculate_cost(&instance),
+        outcome.iterations
     );
 
     if !adaptive_local_search {
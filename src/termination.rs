@@ -0,0 +1,208 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Why a solver stopped searching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StopReason {
+    TimeLimit,
+    IterationLimit,
+    EvaluationLimit,
+    TargetReached,
+    Stagnation,
+    Cancelled,
+    /// The algorithm ran out of work on its own (e.g. local optimum reached).
+    Converged,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            StopReason::TimeLimit => "time limit",
+            StopReason::IterationLimit => "iteration limit",
+            StopReason::EvaluationLimit => "evaluation limit",
+            StopReason::TargetReached => "target cost reached",
+            StopReason::Stagnation => "stagnation",
+            StopReason::Cancelled => "cancelled",
+            StopReason::Converged => "converged",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// Shared flag a controlling thread (or signal handler) sets to stop a running search.
+/// Solvers return the best solution found so far when it fires.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns a token cancelled by the first Ctrl+C; a second Ctrl+C exits immediately.
+    pub fn cancel_on_ctrl_c() -> Result<Self, ctrlc::Error> {
+        let token = Self::new();
+        let handler_token = token.clone();
+        ctrlc::set_handler(move || {
+            if handler_token.is_cancelled() {
+                std::process::exit(130);
+            }
+            eprintln!("\nCancelling, finishing with the best solution found so far...");
+            handler_token.cancel();
+        })?;
+        Ok(token)
+    }
+}
+
+/// Counters a solver maintains while searching; termination criteria are checked against it.
+#[derive(Debug, Clone)]
+pub struct SearchProgress {
+    start_time: Instant,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub best_cost: i32,
+    pub last_improvement_iteration: usize,
+}
+
+impl SearchProgress {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+            iterations: 0,
+            evaluations: 0,
+            best_cost: i32::MAX,
+            last_improvement_iteration: 0,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Counts one evaluated solution and returns `true` if it improved the best cost.
+    pub fn record_evaluation(&mut self, cost: i32) -> bool {
+        self.evaluations += 1;
+        if cost < self.best_cost {
+            self.best_cost = cost;
+            self.last_improvement_iteration = self.iterations;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn iterations_without_improvement(&self) -> usize {
+        self.iterations - self.last_improvement_iteration
+    }
+}
+
+impl Default for SearchProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Composable stopping condition accepted by every `BudgetedAlgorithm`.
+#[derive(Debug, Clone)]
+pub enum Termination {
+    /// Wall-clock limit measured from the start of the run.
+    TimeLimit(Duration),
+    /// Maximum number of main-loop iterations.
+    MaxIterations(usize),
+    /// Maximum number of evaluated candidate solutions (initial ones included).
+    MaxEvaluations(usize),
+    /// Stop as soon as the best cost is at most this value.
+    TargetCost(i32),
+    /// Stop after this many iterations without improving the best cost.
+    Stagnation(usize),
+    /// Stop once the token is cancelled by another thread.
+    Cancelled(CancellationToken),
+    /// Stop when any of the criteria is met.
+    Any(Vec<Termination>),
+    /// Stop when all of the criteria are met.
+    All(Vec<Termination>),
+}
+
+impl Termination {
+    /// Returns the reason to stop, or `None` if the search should continue.
+    pub fn check(&self, progress: &SearchProgress) -> Option<StopReason> {
+        match self {
+            Termination::TimeLimit(limit) => {
+                (progress.elapsed() >= *limit).then_some(StopReason::TimeLimit)
+            }
+            Termination::MaxIterations(limit) => {
+                (progress.iterations >= *limit).then_some(StopReason::IterationLimit)
+            }
+            Termination::MaxEvaluations(limit) => {
+                (progress.evaluations >= *limit).then_some(StopReason::EvaluationLimit)
+            }
+            Termination::TargetCost(target) => {
+                (progress.best_cost <= *target).then_some(StopReason::TargetReached)
+            }
+            Termination::Stagnation(window) => (progress.iterations_without_improvement()
+                >= *window)
+                .then_some(StopReason::Stagnation),
+            Termination::Cancelled(token) => token.is_cancelled().then_some(StopReason::Cancelled),
+            Termination::Any(criteria) => criteria.iter().find_map(|c| c.check(progress)),
+            Termination::All(criteria) => {
+                if criteria.is_empty() {
+                    return None;
+                }
+                let mut first_reason = None;
+                for criterion in criteria {
+                    let reason = criterion.check(progress)?;
+                    first_reason.get_or_insert(reason);
+                }
+                first_reason
+            }
+        }
+    }
+
+    /// The cancellation tokens that stop this criterion on their own, as a new criterion.
+    /// Nested searches (e.g. local search inside a metaheuristic) use it so that they can
+    /// be interrupted without inheriting limits measured against the outer run.
+    pub fn cancellation_only(&self) -> Termination {
+        fn collect(termination: &Termination, tokens: &mut Vec<Termination>) {
+            match termination {
+                Termination::Cancelled(_) => tokens.push(termination.clone()),
+                Termination::Any(criteria) => {
+                    criteria.iter().for_each(|c| collect(c, tokens));
+                }
+                _ => {}
+            }
+        }
+        let mut tokens = Vec::new();
+        collect(self, &mut tokens);
+        Termination::Any(tokens)
+    }
+
+    pub fn or(self, other: Termination) -> Termination {
+        match self {
+            Termination::Any(mut criteria) => {
+                criteria.push(other);
+                Termination::Any(criteria)
+            }
+            first => Termination::Any(vec![first, other]),
+        }
+    }
+
+    pub fn and(self, other: Termination) -> Termination {
+        match self {
+            Termination::All(mut criteria) => {
+                criteria.push(other);
+                Termination::All(criteria)
+            }
+            first => Termination::All(vec![first, other]),
+        }
+    }
+}
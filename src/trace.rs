@@ -0,0 +1,122 @@
+use crate::algorithm::ProgressEvent;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// State of a run at the end of one main-loop iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TracePoint {
+    pub elapsed: Duration,
+    pub iteration: usize,
+    pub best_cost: i32,
+    pub current_cost: i32,
+}
+
+/// Anytime behaviour of a single run, ordered by iteration.
+#[derive(Debug, Clone, Default)]
+pub struct ConvergenceTrace {
+    pub points: Vec<TracePoint>,
+}
+
+impl ConvergenceTrace {
+    /// Best cost known at `time`, or `None` if the run had not finished an iteration yet.
+    pub fn best_cost_at(&self, time: Duration) -> Option<i32> {
+        let idx = self.points.partition_point(|p| p.elapsed <= time);
+        idx.checked_sub(1).map(|i| self.points[i].best_cost)
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.points.last().map_or(Duration::ZERO, |p| p.elapsed)
+    }
+}
+
+/// Builds a `ConvergenceTrace` from the progress events of a run.
+///
+/// Only top-level `IterationFinished` events are recorded, so nested local searches
+/// do not show up as iterations of the enclosing metaheuristic. A point is kept only when
+/// the best cost changes, plus the last iteration of the run so the trace spans all of it.
+#[derive(Debug)]
+pub struct TraceRecorder {
+    start_time: Instant,
+    trace: ConvergenceTrace,
+    /// Latest iteration that did not change the best cost.
+    last_point: Option<TracePoint>,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+            trace: ConvergenceTrace::default(),
+            last_point: None,
+        }
+    }
+
+    pub fn record(&mut self, event: &ProgressEvent) {
+        if let ProgressEvent::IterationFinished {
+            iteration,
+            current_cost,
+            best_cost,
+        } = *event
+        {
+            let point = TracePoint {
+                elapsed: self.start_time.elapsed(),
+                iteration,
+                best_cost,
+                current_cost,
+            };
+            if self.trace.points.last().is_none_or(|p| p.best_cost != best_cost) {
+                self.trace.points.push(point);
+                self.last_point = None;
+            } else {
+                self.last_point = Some(point);
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> ConvergenceTrace {
+        if let Some(point) = self.last_point.take() {
+            self.trace.points.push(point);
+        }
+        self.trace
+    }
+}
+
+impl Default for TraceRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes `traces` as CSV with one row per trace point, numbering runs from 1.
+///
+/// Traces of an experiment are available as `stats.runs.iter().filter_map(|r| r.trace.as_ref())`.
+pub fn write_traces_csv<'a>(
+    path: &Path,
+    algorithm_name: &str,
+    traces: impl IntoIterator<Item = &'a ConvergenceTrace>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    // Algorithm names contain commas, so the column is always quoted
+    let quoted_name = format!("\"{}\"", algorithm_name.replace('"', "\"\""));
+    writeln!(
+        writer,
+        "algorithm,run,elapsed_ms,iteration,best_cost,current_cost"
+    )?;
+    for (run, trace) in traces.into_iter().enumerate() {
+        for point in &trace.points {
+            writeln!(
+                writer,
+                "{},{},{:.3},{},{},{}",
+                quoted_name,
+                run + 1,
+                point.elapsed.as_secs_f64() * 1000.0,
+                point.iteration,
+                point.best_cost,
+                point.current_cost
+            )?;
+        }
+    }
+    writer.flush()
+}
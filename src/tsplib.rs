@@ -124,6 +124,20 @@ impl TsplibInstance {
             )));
         }
 
+        Ok(Self::with_coordinates(name, edge_weight_type, coordinates))
+    }
+
+    /// Euclidean (`EUC_2D`) instance over the given points, e.g. a generated one.
+    pub fn from_coordinates(name: impl Into<String>, coordinates: Vec<(f64, f64)>) -> Self {
+        Self::with_coordinates(name.into(), EdgeWeightType::Euc2D, coordinates)
+    }
+
+    fn with_coordinates(
+        name: String,
+        edge_weight_type: EdgeWeightType,
+        coordinates: Vec<(f64, f64)>,
+    ) -> Self {
+        let dimension = coordinates.len();
         let mut instance = Self {
             name,
             dimension,
@@ -133,7 +147,7 @@ impl TsplibInstance {
             nearest_neighbors: vec![Vec::new(); dimension],
         };
         instance.calculate_distance_matrix();
-        Ok(instance)
+        instance
     }
 
     fn calculate_distance_matrix(&mut self) {
@@ -215,6 +229,20 @@ impl TsplibInstance {
     }
 }
 
+/// Cost of traversing an edge, as seen by the move evaluators and `Solution::calculate_cost_with`.
+///
+/// Implemented by `TsplibInstance` (plain distances); metaheuristics that search on a
+/// modified objective, such as guided local search, provide their own implementation.
+pub trait EdgeCost {
+    fn edge_cost(&self, from: usize, to: usize) -> i32;
+}
+
+impl EdgeCost for TsplibInstance {
+    fn edge_cost(&self, from: usize, to: usize) -> i32 {
+        self.distance(from, to)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Solution {
     pub cycle1: Vec<usize>,
@@ -227,12 +255,17 @@ impl Solution {
     }
 
     pub fn calculate_cost(&self, instance: &TsplibInstance) -> i32 {
-        let cost1 = self.calculate_cycle_cost(&self.cycle1, instance);
-        let cost2 = self.calculate_cycle_cost(&self.cycle2, instance);
+        self.calculate_cost_with(instance)
+    }
+
+    /// Cost of the solution under arbitrary edge costs, e.g. a penalized objective.
+    pub fn calculate_cost_with<C: EdgeCost + ?Sized>(&self, costs: &C) -> i32 {
+        let cost1 = self.calculate_cycle_cost(&self.cycle1, costs);
+        let cost2 = self.calculate_cycle_cost(&self.cycle2, costs);
         cost1 + cost2
     }
 
-    fn calculate_cycle_cost(&self, cycle: &[usize], instance: &TsplibInstance) -> i32 {
+    fn calculate_cycle_cost<C: EdgeCost + ?Sized>(&self, cycle: &[usize], costs: &C) -> i32 {
         if cycle.is_empty() {
             return 0;
         }
@@ -240,7 +273,7 @@ impl Solution {
         for i in 0..cycle.len() {
             let from = cycle[i];
             let to = cycle[(i + 1) % cycle.len()];
-            cost += instance.distance(from, to);
+            cost += costs.edge_cost(from, to);
         }
         cost
     }
@@ -1,9 +1,12 @@
+use crate::trace::ConvergenceTrace;
 use crate::tsplib::{Solution, TsplibInstance};
 use plotters::prelude::*;
 use std::path::Path;
+use std::time::Duration;
 
 const POINT_SIZE: u32 = 3;
 const LINE_WIDTH: u32 = 2;
+const CONVERGENCE_SAMPLES: usize = 200;
 
 pub fn plot_solution(
     instance: &TsplibInstance,
@@ -94,3 +97,117 @@ pub fn plot_solution(
 
     Ok(())
 }
+
+/// Best cost over runs at one sample time (in seconds).
+#[derive(Clone, Copy)]
+struct BandPoint {
+    time: f64,
+    mean: f64,
+    min: f64,
+    max: f64,
+}
+
+/// Samples the best cost over the runs that had finished at least one iteration by each time.
+fn convergence_band(traces: &[ConvergenceTrace], max_time: Duration) -> Vec<BandPoint> {
+    (0..CONVERGENCE_SAMPLES)
+        .filter_map(|k| {
+            let time = max_time.mul_f64(k as f64 / (CONVERGENCE_SAMPLES - 1) as f64);
+            let costs: Vec<i32> = traces.iter().filter_map(|t| t.best_cost_at(time)).collect();
+            if costs.is_empty() {
+                return None;
+            }
+            let mean = costs.iter().map(|&c| c as f64).sum::<f64>() / costs.len() as f64;
+            let min = *costs.iter().min().unwrap() as f64;
+            let max = *costs.iter().max().unwrap() as f64;
+            Some(BandPoint {
+                time: time.as_secs_f64(),
+                mean,
+                min,
+                max,
+            })
+        })
+        .collect()
+}
+
+/// Plots best cost over time for each algorithm: the mean over its runs as a line and
+/// the min/max over its runs as a shaded band.
+pub fn plot_convergence(
+    series: &[(&str, &[ConvergenceTrace])],
+    title: &str,
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let max_time = series
+        .iter()
+        .flat_map(|(_, traces)| traces.iter().map(ConvergenceTrace::duration))
+        .max()
+        .unwrap_or(Duration::ZERO)
+        .max(Duration::from_millis(1));
+
+    let bands: Vec<(&str, Vec<BandPoint>)> = series
+        .iter()
+        .map(|(name, traces)| (*name, convergence_band(traces, max_time)))
+        .collect();
+
+    let (min_cost, max_cost) = bands
+        .iter()
+        .flat_map(|(_, band)| band.iter())
+        .fold((f64::MAX, f64::MIN), |acc, p| {
+            (acc.0.min(p.min), acc.1.max(p.max))
+        });
+    if min_cost > max_cost {
+        return Err("no trace points to plot".into());
+    }
+    let padding = (max_cost - min_cost).max(1.0) * 0.05;
+
+    let root = BitMapBackend::new(output_path, (1000, 700)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(
+            0.0..max_time.as_secs_f64(),
+            (min_cost - padding)..(max_cost + padding),
+        )?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Time [s]")
+        .y_desc("Best cost")
+        .draw()?;
+
+    for (i, (name, band)) in bands.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+
+        let outline: Vec<(f64, f64)> = band
+            .iter()
+            .map(|p| (p.time, p.max))
+            .chain(band.iter().rev().map(|p| (p.time, p.min)))
+            .collect();
+        chart.draw_series(std::iter::once(Polygon::new(
+            outline,
+            color.mix(0.2).filled(),
+        )))?;
+
+        chart
+            .draw_series(LineSeries::new(
+                band.iter().map(|p| (p.time, p.mean)),
+                color.stroke_width(LINE_WIDTH),
+            ))?
+            .label(*name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::UpperRight)
+        .draw()?;
+
+    root.present()?;
+
+    Ok(())
+}